@@ -1,4 +1,4 @@
-use levenshtein_lite::LevenshteinAutomata;
+use levenshtein_lite::{LevenshteinAutomata, LazyLevenshteinAutomata, levenshtein_distance, DistanceCalculator};
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
 
 // Benchmarks for d=0
@@ -85,6 +85,44 @@ fn bench_check_d4_no_match(c: &mut Criterion) {
     });
 }
 
+// Benchmarks for one-vs-many distances
+const CANDIDATES: [&str; 8] = [
+    "Levenshtein", "Lewenstein", "Levenshtien", "Leviathan",
+    "Einstein", "Frankenstein", "levenshtein", "Levenshteinx"
+];
+
+fn bench_distance_many_naive(c: &mut Criterion) {
+    c.bench_function("distance_many_naive", |b| {
+        b.iter(|| {
+            CANDIDATES
+                .iter()
+                .map(|cand| levenshtein_distance(black_box("Levenshtein"), cand))
+                .collect::<Vec<i32>>()
+        });
+    });
+}
+
+fn bench_distance_many_calculator(c: &mut Criterion) {
+    let mut calc = DistanceCalculator::new("Levenshtein");
+    c.bench_function("distance_many_calculator", |b| {
+        b.iter(|| calc.distance_many(black_box(CANDIDATES)));
+    });
+}
+
+// Benchmarks for lazy construction at higher distances
+fn bench_instantiation_lazy_d4(c: &mut Criterion) {
+    c.bench_function("instantiation_lazy_d4", |b| {
+        b.iter(|| LazyLevenshteinAutomata::new(black_box("Levenshtein distance"), black_box(4)));
+    });
+}
+
+fn bench_check_lazy_d4_match(c: &mut Criterion) {
+    let mut lda = LazyLevenshteinAutomata::new("Levenshtein distance", 4);
+    c.bench_function("check_lazy_d4_match", |b| {
+        b.iter(|| lda.check(black_box("Levenstein distanse")));
+    });
+}
+
 criterion_group!(
     benches,
     bench_instantiation_d0,
@@ -99,5 +137,9 @@ criterion_group!(
     bench_instantiation_d4,
     bench_check_d4_match,
     bench_check_d4_no_match,
+    bench_distance_many_naive,
+    bench_distance_many_calculator,
+    bench_instantiation_lazy_d4,
+    bench_check_lazy_d4_match,
 );
 criterion_main!(benches);
\ No newline at end of file
@@ -0,0 +1,152 @@
+//! Derive macro for typo-tolerant enum parsing with `levenshtein_lite`
+//!
+//! Enable it through the `derive` feature of `levenshtein_lite`,
+//! rather than depending on this crate directly.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{parse_macro_input, Attribute, Data, DeriveInput, Fields, LitBool, LitInt, LitStr};
+
+/// Derives a `FromStr` implementation for an enum of unit variants
+/// that tolerates typos in the variant names
+///
+/// Unknown names produce a `levenshtein_lite::fuzzy_parse::ParseFuzzyError`
+/// suggesting the closest variant, e.g.
+/// `unknown variant 'warnnig', did you mean 'Warning'?`.
+///
+/// # Attributes
+/// On the enum:
+/// * `#[fuzzy(max_distance = 2)]` - names up to this distance are suggested
+/// * `#[fuzzy(accept_distance = 1)]` - names up to this distance are
+///   accepted outright, if no other variant is as close (default `0`)
+/// * `#[fuzzy(case_insensitive)]` - compare names in lowercase
+///
+/// On a variant:
+/// * `#[fuzzy(rename = "warning")]` - parse this name instead of the variant's
+/// * `#[fuzzy(alias = "warn")]` - also parse this name; may be repeated
+///
+/// # Examples
+/// ```
+/// # use levenshtein_lite_derive::FuzzyFromStr;
+/// // with the `derive` feature: use levenshtein_lite::FuzzyFromStr;
+///
+/// #[derive(FuzzyFromStr)]
+/// #[fuzzy(case_insensitive, accept_distance = 1)]
+/// enum Level {
+///     #[fuzzy(alias = "warn")]
+///     Warning,
+///     Error
+/// }
+///
+/// assert!(matches!("warnig".parse(), Ok(Level::Warning)));
+/// ```
+#[proc_macro_derive(FuzzyFromStr, attributes(fuzzy))]
+pub fn derive_fuzzy_from_str(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(input)
+        .unwrap_or_else(|err| err.to_compile_error())
+        .into()
+}
+
+struct EnumOptions {
+    max_distance: i8,
+    accept_distance: i8,
+    case_insensitive: bool
+}
+
+fn enum_options(attrs: &[Attribute]) -> syn::Result<EnumOptions> {
+    let mut options = EnumOptions {
+        max_distance: 2,
+        accept_distance: 0,
+        case_insensitive: false
+    };
+    for attr in attrs.iter().filter(|a| a.path().is_ident("fuzzy")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("max_distance") {
+                options.max_distance = meta.value()?.parse::<LitInt>()?.base10_parse()?;
+            } else if meta.path.is_ident("accept_distance") {
+                options.accept_distance = meta.value()?.parse::<LitInt>()?.base10_parse()?;
+            } else if meta.path.is_ident("case_insensitive") {
+                options.case_insensitive = match meta.value() {
+                    Ok(value) => value.parse::<LitBool>()?.value,
+                    Err(_) => true
+                };
+            } else {
+                return Err(meta.error("expected `max_distance`, `accept_distance` or `case_insensitive`"));
+            }
+            Ok(())
+        })?;
+    }
+    Ok(options)
+}
+
+// Returns the names a variant parses from, its canonical name first
+fn variant_names(variant: &syn::Variant) -> syn::Result<Vec<String>> {
+    let mut name = variant.ident.to_string();
+    let mut aliases = vec![];
+    for attr in variant.attrs.iter().filter(|a| a.path().is_ident("fuzzy")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("rename") {
+                name = meta.value()?.parse::<LitStr>()?.value();
+            } else if meta.path.is_ident("alias") {
+                aliases.push(meta.value()?.parse::<LitStr>()?.value());
+            } else {
+                return Err(meta.error("expected `rename` or `alias`"));
+            }
+            Ok(())
+        })?;
+    }
+    let mut names = vec![name];
+    names.extend(aliases);
+    Ok(names)
+}
+
+fn expand(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let data = match &input.data {
+        Data::Enum(data) => data,
+        _ => return Err(syn::Error::new_spanned(
+            &input.ident,
+            "FuzzyFromStr can only be derived for enums"
+        ))
+    };
+    let options = enum_options(&input.attrs)?;
+
+    let mut names = vec![];
+    let mut arms = vec![];
+    for (idx, variant) in data.variants.iter().enumerate() {
+        if !matches!(variant.fields, Fields::Unit) {
+            return Err(syn::Error::new_spanned(
+                variant,
+                "FuzzyFromStr only supports unit variants"
+            ));
+        }
+        for name in variant_names(variant)? {
+            names.push(quote! { (#name, #idx) });
+        }
+        let ident = &variant.ident;
+        arms.push(quote! { #idx => ::core::result::Result::Ok(Self::#ident) });
+    }
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let EnumOptions { max_distance, accept_distance, case_insensitive } = options;
+    Ok(quote! {
+        impl #impl_generics ::core::str::FromStr for #ident #ty_generics #where_clause {
+            type Err = ::levenshtein_lite::fuzzy_parse::ParseFuzzyError;
+
+            fn from_str(s: &str) -> ::core::result::Result<Self, Self::Err> {
+                const NAMES: &[(&str, usize)] = &[#(#names),*];
+                let options = ::levenshtein_lite::fuzzy_parse::ResolveOptions {
+                    max_distance: #max_distance,
+                    accept_distance: #accept_distance,
+                    case_insensitive: #case_insensitive
+                };
+                match ::levenshtein_lite::fuzzy_parse::resolve(s, NAMES, options)? {
+                    #(#arms,)*
+                    _ => ::core::unreachable!()
+                }
+            }
+        }
+    })
+}
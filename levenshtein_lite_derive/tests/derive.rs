@@ -0,0 +1,46 @@
+use std::str::FromStr;
+use levenshtein_lite_derive::FuzzyFromStr;
+
+#[derive(Debug, PartialEq, FuzzyFromStr)]
+enum Strict {
+    Warning,
+    Error
+}
+
+#[derive(Debug, PartialEq, FuzzyFromStr)]
+#[fuzzy(case_insensitive, accept_distance = 1, max_distance = 3)]
+enum Level {
+    #[fuzzy(rename = "warning", alias = "warn", alias = "w")]
+    Warning,
+    #[fuzzy(rename = "error")]
+    Error,
+    #[fuzzy(rename = "info")]
+    Info
+}
+
+#[test]
+fn strict_parsing() {
+    assert!(Strict::from_str("Warning") == Ok(Strict::Warning));
+    assert!(Strict::from_str("Error") == Ok(Strict::Error));
+
+    let err = Strict::from_str("Warnnig").unwrap_err();
+    assert!(err.to_string() == "unknown variant 'Warnnig', did you mean 'Warning'?");
+    // near misses are only suggested, never accepted, by default
+    assert!(Strict::from_str("Eror").unwrap_err().suggestion() == Some("Error"));
+    assert!(Strict::from_str("warning").unwrap_err().suggestion() == Some("Warning"));
+    assert!(Strict::from_str("Debug").unwrap_err().suggestion().is_none());
+}
+
+#[test]
+fn configured_parsing() {
+    assert!("warning".parse::<Level>() == Ok(Level::Warning));
+    assert!("WARN".parse::<Level>() == Ok(Level::Warning));
+    assert!("w".parse::<Level>() == Ok(Level::Warning));
+    assert!("Warnig".parse::<Level>() == Ok(Level::Warning));
+    assert!("eror".parse::<Level>() == Ok(Level::Error));
+
+    // three edits away is within max_distance, so only suggested
+    let err = "inxxx".parse::<Level>().unwrap_err();
+    assert!(err.suggestion() == Some("info"));
+    assert!(err.to_string() == "unknown variant 'inxxx', did you mean 'info'?");
+}
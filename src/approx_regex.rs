@@ -0,0 +1,648 @@
+use std::error::Error;
+use std::fmt;
+use crate::levenshtein_automata::Dacc;
+
+// repeat counts above this are rejected, as each repetition is
+// expanded into its own copy of the repeated expression
+const MAX_REPEAT: u32 = 1000;
+// nested repeats multiply, so the expanded pattern is bounded as a whole;
+// each symbol left after expansion is a position of the automaton
+const MAX_POSITIONS: usize = 100_000;
+// optional symbols can each be followed by all the ones after them,
+// so the follow lists are bounded too, as they can grow quadratically
+const MAX_FOLLOWS: usize = 1_000_000;
+
+// (state, accumulated distance) pairs, over the states of an `ApproxRegex`
+type StateDaccPairs = Vec<(usize, Dacc)>;
+
+/// The error returned when a pattern isn't in the supported regex subset
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ParseRegexError {
+    position: usize,
+    message: &'static str
+}
+
+impl ParseRegexError {
+    /// Returns the char index in the pattern where parsing failed
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Returns a description of the problem
+    pub fn message(&self) -> &str {
+        self.message
+    }
+}
+
+impl fmt::Display for ParseRegexError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid pattern at position {}: {}", self.position, self.message)
+    }
+}
+
+impl Error for ParseRegexError {}
+
+#[derive(Clone, Debug)]
+struct Class {
+    negated: bool,
+    ranges: Vec<(char, char)>
+}
+
+impl Class {
+    fn literal(c: char) -> Self {
+        Class { negated: false, ranges: vec![(c, c)] }
+    }
+
+    fn matches(&self, c: char) -> bool {
+        self.ranges.iter().any(|&(lo, hi)| lo <= c && c <= hi) != self.negated
+    }
+}
+
+#[derive(Clone, Debug)]
+enum Node {
+    Empty,
+    Symbol(Class),
+    Concat(Vec<Node>),
+    Alt(Vec<Node>),
+    Star(Box<Node>),
+    Optional(Box<Node>)
+}
+
+struct Parser {
+    chars: Vec<char>,
+    pos: usize,
+    // the symbols in the nodes parsed so far, once repeats are expanded
+    positions: usize
+}
+
+impl Parser {
+    fn error<T>(&self, message: &'static str) -> Result<T, ParseRegexError> {
+        Err(ParseRegexError { position: self.pos, message })
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        if self.peek() == Some(c) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn parse(mut self) -> Result<Node, ParseRegexError> {
+        let node = self.parse_alt()?;
+        if self.pos < self.chars.len() {
+            return self.error("unmatched ')'");
+        }
+        Ok(node)
+    }
+
+    fn parse_alt(&mut self) -> Result<Node, ParseRegexError> {
+        let mut branches = vec![self.parse_concat()?];
+        while self.eat('|') {
+            branches.push(self.parse_concat()?);
+        }
+        Ok(if branches.len() == 1 { branches.pop().unwrap() } else { Node::Alt(branches) })
+    }
+
+    fn parse_concat(&mut self) -> Result<Node, ParseRegexError> {
+        let mut parts = vec![];
+        while let Some(c) = self.peek() {
+            if c == '|' || c == ')' {
+                break;
+            }
+            parts.push(self.parse_repeat()?);
+        }
+        Ok(match parts.len() {
+            0 => Node::Empty,
+            1 => parts.pop().unwrap(),
+            _ => Node::Concat(parts)
+        })
+    }
+
+    fn parse_repeat(&mut self) -> Result<Node, ParseRegexError> {
+        let before = self.positions;
+        let mut node = self.parse_atom()?;
+        let mut size = self.positions - before;
+        loop {
+            let start = self.pos;
+            node = match self.peek() {
+                Some('?') => Node::Optional(Box::new(node)),
+                Some('*') => Node::Star(Box::new(node)),
+                Some('+') => {
+                    self.add_positions(size, start)?;
+                    size *= 2;
+                    Node::Concat(vec![node.clone(), Node::Star(Box::new(node))])
+                },
+                Some('{') => {
+                    let (min, max) = self.parse_bounds()?;
+                    let copies = max.unwrap_or(min + 1) as usize;
+                    // the copies replace the original, and are
+                    // accounted for before they are made
+                    self.positions -= size;
+                    size *= copies;
+                    self.add_positions(size, start)?;
+                    // bounded repeats are spelled out, e.g. x{2,3} as xxx?
+                    let mut parts = vec![node.clone(); min as usize];
+                    match max {
+                        Some(max) => {
+                            let optional = Node::Optional(Box::new(node));
+                            parts.extend(vec![optional; (max - min) as usize]);
+                        },
+                        None => parts.push(Node::Star(Box::new(node)))
+                    }
+                    Node::Concat(parts)
+                },
+                _ => break
+            };
+            // the quantifier, or the closing brace of the bounds
+            self.pos += 1;
+        }
+        Ok(node)
+    }
+
+    // Accounts for new symbols, failing at `start`
+    // once the pattern grows too large
+    fn add_positions(&mut self, added: usize, start: usize) -> Result<(), ParseRegexError> {
+        self.positions += added;
+        if self.positions > MAX_POSITIONS {
+            self.pos = start;
+            return self.error("pattern is too large");
+        }
+        Ok(())
+    }
+
+    // Parses `{n}`, `{n,}` or `{n,m}`, leaving the closing brace to the caller
+    fn parse_bounds(&mut self) -> Result<(u32, Option<u32>), ParseRegexError> {
+        let start = self.pos;
+        self.pos += 1;
+        let min = self.parse_number()?;
+        let max = if self.eat(',') {
+            match self.peek() {
+                Some('}') => None,
+                _ => Some(self.parse_number()?)
+            }
+        } else {
+            Some(min)
+        };
+        if self.peek() != Some('}') {
+            return self.error("expected '}'");
+        }
+        if max.is_some_and(|max| max < min) {
+            self.pos = start;
+            return self.error("repeat bounds are out of order");
+        }
+        if max.unwrap_or(min) > MAX_REPEAT {
+            self.pos = start;
+            return self.error("repeat count is too large");
+        }
+        Ok((min, max))
+    }
+
+    fn parse_number(&mut self) -> Result<u32, ParseRegexError> {
+        let start = self.pos;
+        while self.peek().is_some_and(|c| c.is_ascii_digit()) {
+            self.pos += 1;
+        }
+        if start == self.pos {
+            return self.error("expected a number");
+        }
+        let digits = self.chars[start..self.pos].iter().collect::<String>();
+        match digits.parse() {
+            Ok(n) => Ok(n),
+            Err(_) => {
+                self.pos = start;
+                self.error("repeat count is too large")
+            }
+        }
+    }
+
+    fn parse_atom(&mut self) -> Result<Node, ParseRegexError> {
+        // only called with a char left to parse
+        let start = self.pos;
+        let c = self.chars[self.pos];
+        if !matches!(c, '(' | '?' | '*' | '+' | '{') {
+            // every other atom is a single symbol
+            self.add_positions(1, start)?;
+        }
+        match c {
+            '(' => {
+                self.pos += 1;
+                let node = self.parse_alt()?;
+                if !self.eat(')') {
+                    return self.error("unclosed group");
+                }
+                Ok(node)
+            },
+            '[' => {
+                self.pos += 1;
+                Ok(Node::Symbol(self.parse_class()?))
+            },
+            '.' => {
+                self.pos += 1;
+                Ok(Node::Symbol(Class { negated: true, ranges: vec![] }))
+            },
+            '\\' => {
+                self.pos += 1;
+                Ok(Node::Symbol(self.parse_escape()?))
+            },
+            '?' | '*' | '+' | '{' => self.error("nothing to repeat"),
+            _ => {
+                self.pos += 1;
+                Ok(Node::Symbol(Class::literal(c)))
+            }
+        }
+    }
+
+    // Parses the escape after a backslash
+    fn parse_escape(&mut self) -> Result<Class, ParseRegexError> {
+        let c = match self.peek() {
+            Some(c) => c,
+            None => return self.error("trailing backslash")
+        };
+        self.pos += 1;
+        let ranges = match c {
+            'd' => vec![('0', '9')],
+            'w' => vec![('a', 'z'), ('A', 'Z'), ('0', '9'), ('_', '_')],
+            's' => vec![(' ', ' '), ('\t', '\r')],
+            'n' => vec![('\n', '\n')],
+            't' => vec![('\t', '\t')],
+            _ => vec![(c, c)]
+        };
+        Ok(Class { negated: false, ranges })
+    }
+
+    // Parses a class after its opening bracket
+    fn parse_class(&mut self) -> Result<Class, ParseRegexError> {
+        let negated = self.eat('^');
+        let mut ranges = vec![];
+        loop {
+            let item_start = self.pos;
+            let lo = match self.peek() {
+                None => return self.error("unclosed class"),
+                Some(']') => {
+                    self.pos += 1;
+                    break;
+                },
+                Some('\\') => {
+                    self.pos += 1;
+                    let escaped = self.parse_escape()?;
+                    if escaped.ranges.len() > 1 || escaped.ranges[0].0 != escaped.ranges[0].1 {
+                        ranges.extend(escaped.ranges);
+                        continue;
+                    }
+                    escaped.ranges[0].0
+                },
+                Some(c) => {
+                    self.pos += 1;
+                    c
+                }
+            };
+            let is_range = self.peek() == Some('-')
+                && self.chars.get(self.pos + 1).is_some_and(|&c| c != ']');
+            if is_range {
+                self.pos += 1;
+                let hi = self.chars[self.pos];
+                if hi < lo {
+                    self.pos = item_start;
+                    return self.error("class range is out of order");
+                }
+                self.pos += 1;
+                ranges.push((lo, hi));
+            } else {
+                ranges.push((lo, lo));
+            }
+        }
+        Ok(Class { negated, ranges })
+    }
+}
+
+// Records that each of the `from` positions may be followed by the `to` ones,
+// or returns `None` if that takes the follow lists past `MAX_FOLLOWS`
+fn link(follow: &mut [Vec<usize>], follows: &mut usize, from: &[usize], to: &[usize]) -> Option<()> {
+    *follows += from.len() * to.len();
+    if *follows > MAX_FOLLOWS {
+        return None;
+    }
+    for &pos in from {
+        follow[pos].extend_from_slice(to);
+    }
+    Some(())
+}
+
+// Builds the Glushkov automaton of `node`, numbering its symbols as positions
+// and recording which positions may follow each other.
+// Returns whether `node` matches the empty string, and its first and last positions,
+// or `None` if the automaton grows too large
+fn glushkov(
+    node: &Node,
+    classes: &mut Vec<Class>,
+    follow: &mut Vec<Vec<usize>>,
+    follows: &mut usize
+) -> Option<(bool, Vec<usize>, Vec<usize>)> {
+    Some(match node {
+        Node::Empty => (true, vec![], vec![]),
+        Node::Symbol(class) => {
+            let pos = classes.len();
+            classes.push(class.clone());
+            follow.push(vec![]);
+            (false, vec![pos], vec![pos])
+        },
+        Node::Concat(parts) => {
+            let (mut nullable, mut first, mut last) = (true, vec![], Vec::<usize>::new());
+            for part in parts {
+                let (part_nullable, part_first, part_last) = glushkov(part, classes, follow, follows)?;
+                link(follow, follows, &last, &part_first)?;
+                if nullable {
+                    first.extend_from_slice(&part_first);
+                }
+                if part_nullable {
+                    last.extend(part_last);
+                } else {
+                    last = part_last;
+                }
+                nullable &= part_nullable;
+            }
+            (nullable, first, last)
+        },
+        Node::Alt(branches) => {
+            let (mut nullable, mut first, mut last) = (false, vec![], vec![]);
+            for branch in branches {
+                let (branch_nullable, branch_first, branch_last) = glushkov(branch, classes, follow, follows)?;
+                nullable |= branch_nullable;
+                first.extend(branch_first);
+                last.extend(branch_last);
+            }
+            (nullable, first, last)
+        },
+        Node::Star(inner) => {
+            let (_, first, last) = glushkov(inner, classes, follow, follows)?;
+            link(follow, follows, &last, &first)?;
+            (true, first, last)
+        },
+        Node::Optional(inner) => {
+            let (_, first, last) = glushkov(inner, classes, follow, follows)?;
+            (true, first, last)
+        }
+    })
+}
+
+/// Matches strings against a regular expression, tolerating edits
+///
+/// Supported syntax: literals, `.`, classes such as `[a-z_]` and `[^0-9]`,
+/// the escapes `\d`, `\w`, `\s`, `\n` and `\t`, groups, alternation with `|`,
+/// and the repeats `?`, `*`, `+`, `{n}`, `{n,}` and `{n,m}`.
+///
+/// Patterns match whole inputs, as though anchored with `^...$`.
+pub struct ApproxRegex {
+    pattern: String,
+    max_distance: i8,
+    // state 0 is the start state, and state p + 1 the pattern position p
+    classes: Vec<Class>,
+    follow: Vec<Vec<usize>>,
+    accepting: Vec<bool>
+}
+
+impl ApproxRegex {
+    /// Compiles a pattern into a new matcher
+    ///
+    /// Repeat counts are limited to 1000, and patterns to 100000
+    /// symbols once their bounded repeats are spelled out, and to
+    /// 1000000 pairs of symbols that may follow each other.
+    ///
+    /// # Arguments
+    /// * `pattern` - the regular expression inputs will be matched with
+    /// * `max_distance` - the maximum acceptable Levenshtein Distance between
+    ///   an input and the closest string the pattern matches
+    ///
+    /// # Returns
+    /// * A new `ApproxRegex` instance, or the reason the pattern is invalid
+    ///
+    /// # Examples
+    /// ```
+    /// use levenshtein_lite::ApproxRegex;
+    /// let re = ApproxRegex::new("ERR(OR)?-[0-9]{4}", 1).unwrap();
+    /// assert!(re.check("ERROR-1234"));
+    /// assert!(re.check("EROR-1234"));
+    /// assert!(re.check("ERR-123"));
+    /// assert!(!re.check("WARN-1234"));
+    /// assert!(ApproxRegex::new("ERR(OR-", 1).is_err());
+    /// ```
+    pub fn new(pattern: &str, max_distance: i8) -> Result<Self, ParseRegexError> {
+        let parser = Parser {
+            chars: pattern.chars().collect(),
+            pos: 0,
+            positions: 0
+        };
+        let node = parser.parse()?;
+        let mut classes = vec![];
+        let mut position_follow = vec![];
+        let (nullable, first, last) = match glushkov(&node, &mut classes, &mut position_follow, &mut 0) {
+            Some(glushkov) => glushkov,
+            None => return Err(ParseRegexError { position: 0, message: "pattern is too large" })
+        };
+
+        let mut follow = vec![first.iter().map(|&pos| pos + 1).collect::<Vec<usize>>()];
+        for mut next in position_follow {
+            next.sort();
+            next.dedup();
+            follow.push(next.into_iter().map(|pos| pos + 1).collect());
+        }
+        let mut accepting = vec![false; classes.len() + 1];
+        accepting[0] = nullable;
+        for pos in last {
+            accepting[pos + 1] = true;
+        }
+        Ok(ApproxRegex {
+            pattern: pattern.to_string(),
+            max_distance,
+            classes,
+            follow,
+            accepting
+        })
+    }
+
+    /// Returns the parameters of the matcher
+    pub fn details(&self) -> (&str, i8) {
+        (&self.pattern, self.max_distance)
+    }
+
+    /// Checks an input string against the pattern
+    ///
+    /// # Arguments
+    /// * `input` - the string to match
+    ///
+    /// # Returns
+    /// * `true` if some string the pattern matches is within
+    ///   `max_distance` of `input`
+    pub fn check(&self, input: &str) -> bool {
+        let mut pairs = self.closure(vec![(0, 0)]);
+        for c in input.chars() {
+            pairs = self.step(&pairs, c);
+            if pairs.is_empty() {
+                return false;
+            }
+        }
+        pairs.iter().any(|&(state, _)| self.accepting[state])
+    }
+
+    // Reads a char of input from every (state, distance) pair
+    fn step(&self, pairs: &StateDaccPairs, c: char) -> StateDaccPairs {
+        let mut next = vec![];
+        for &(state, dacc) in pairs {
+            for &target in &self.follow[state] {
+                if self.classes[target - 1].matches(c) {
+                    // match: consume a pattern position at zero cost
+                    next.push((target, dacc));
+                } else if dacc < self.max_distance {
+                    // substitution: consume a pattern position at the cost of 1 edit
+                    next.push((target, dacc + 1));
+                }
+            }
+            if dacc < self.max_distance {
+                // insertion: stay at this position at the cost of 1 edit
+                next.push((state, dacc + 1));
+            }
+        }
+        self.closure(next)
+    }
+
+    // Adds the pairs reached by skipping pattern positions, i.e. deletions,
+    // keeping only the smallest distance for each state
+    fn closure(&self, pairs: StateDaccPairs) -> StateDaccPairs {
+        let mut best: Vec<Option<Dacc>> = vec![None; self.follow.len()];
+        let mut pending = pairs;
+        while let Some((state, dacc)) = pending.pop() {
+            if best[state].is_some_and(|best_dacc| best_dacc <= dacc) {
+                continue;
+            }
+            best[state] = Some(dacc);
+            if dacc < self.max_distance {
+                for &target in &self.follow[state] {
+                    pending.push((target, dacc + 1));
+                }
+            }
+        }
+        best.into_iter()
+            .enumerate()
+            .filter_map(|(state, dacc)| dacc.map(|dacc| (state, dacc)))
+            .collect()
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::collections::BTreeSet;
+    use crate::levenshtein_distance;
+    use crate::verify::all_strings;
+
+    // All strings of `node` over `alphabet`, up to `max_len` chars
+    fn language(node: &Node, alphabet: &[char], max_len: usize) -> BTreeSet<String> {
+        let concat = |a: &BTreeSet<String>, b: &BTreeSet<String>| {
+            let mut out = BTreeSet::new();
+            for x in a {
+                for y in b {
+                    if x.chars().count() + y.chars().count() <= max_len {
+                        out.insert(format!("{}{}", x, y));
+                    }
+                }
+            }
+            out
+        };
+        match node {
+            Node::Empty => BTreeSet::from([String::new()]),
+            Node::Symbol(class) => alphabet
+                .iter()
+                .filter(|&&c| class.matches(c) && max_len > 0)
+                .map(|c| c.to_string())
+                .collect(),
+            Node::Concat(parts) => parts.iter().fold(BTreeSet::from([String::new()]), |acc, part| {
+                concat(&acc, &language(part, alphabet, max_len))
+            }),
+            Node::Alt(branches) => branches
+                .iter()
+                .flat_map(|branch| language(branch, alphabet, max_len))
+                .collect(),
+            Node::Star(inner) => {
+                let once = language(inner, alphabet, max_len);
+                let mut all = BTreeSet::from([String::new()]);
+                loop {
+                    let more = concat(&all, &once);
+                    if more.is_subset(&all) {
+                        break all;
+                    }
+                    all.extend(more);
+                }
+            },
+            Node::Optional(inner) => {
+                let mut all = language(inner, alphabet, max_len);
+                all.insert(String::new());
+                all
+            }
+        }
+    }
+
+    #[test]
+    fn agrees_with_brute_force() {
+        let alphabet = ['a', 'b', '1'];
+        let inputs = all_strings(&alphabet, 4);
+        let patterns = [
+            "", "a", "ab", "a|b1", "a(b|1)*", "[ab]+1?", "(ab){2,3}", "a.b",
+            "[^a]b", "\\d{2,}", "(a|)b", "((a*)b)*", "a{0}", "[a-b1]{1,2}a"
+        ];
+        for pattern in patterns {
+            let node = Parser { chars: pattern.chars().collect(), pos: 0, positions: 0 }.parse().unwrap();
+            let words = language(&node, &alphabet, 4 + 2);
+            for max_distance in 0..=2 {
+                let re = ApproxRegex::new(pattern, max_distance).unwrap();
+                for input in &inputs {
+                    let expected = words
+                        .iter()
+                        .any(|word| levenshtein_distance(input, word) <= max_distance as i32);
+                    assert!(re.check(input) == expected, "{:?} {:?} {}", pattern, input, max_distance);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn invalid_patterns() {
+        let cases = [
+            ("ab(c", 4, "unclosed group"),
+            ("ab)c", 2, "unmatched ')'"),
+            ("*a", 0, "nothing to repeat"),
+            ("a{3,2}", 1, "repeat bounds are out of order"),
+            ("a{2", 3, "expected '}'"),
+            ("a{x}", 2, "expected a number"),
+            ("[a-", 3, "unclosed class"),
+            ("[z-a]", 1, "class range is out of order"),
+            ("ab\\", 3, "trailing backslash"),
+            ("a{5000}", 1, "repeat count is too large"),
+            ("((a{1000}){1000}){1000}", 10, "pattern is too large"),
+            ("(ab{1000}){100}", 10, "pattern is too large"),
+            ("(a{100}b+){1000}", 10, "pattern is too large"),
+            // few positions, but each optional one may be followed by all the next
+            ("((a?){1000}){40}", 0, "pattern is too large")
+        ];
+        for (pattern, position, message) in cases {
+            let err = ApproxRegex::new(pattern, 1).err().unwrap();
+            assert!(err.position() == position && err.message() == message, "{:?} {:?}", pattern, err);
+        }
+        // patterns right at the limit are still accepted
+        assert!(ApproxRegex::new("(a{1000}){100}", 0).is_ok());
+
+        let err = ApproxRegex::new("a(", 0).err().unwrap();
+        assert!(err.to_string() == "invalid pattern at position 2: unclosed group");
+
+        // escapes and special chars inside classes
+        let re = ApproxRegex::new("[\\d.-]+\\.[a-z]\\s", 0).unwrap();
+        assert!(re.check("1.-2.x "));
+        assert!(!re.check("1.-2.x"));
+    }
+}
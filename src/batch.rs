@@ -0,0 +1,237 @@
+use std::collections::HashMap;
+
+const WORD_BITS: usize = 64;
+
+/// Computes Levenshtein distances from one query to many candidates
+///
+/// The query is preprocessed once into per-char bitmasks, and the
+/// scratch buffers are reused between candidates, so no allocation
+/// happens per comparison. Distances are computed with Myers'
+/// bit-parallel algorithm, split into 64 char blocks for long queries.
+pub struct DistanceCalculator {
+    query_len: usize,
+    blocks: usize,
+    // bitmasks of the query positions holding each ascii char,
+    // `blocks` words per char
+    ascii_peq: Vec<u64>,
+    peq: HashMap<char, Vec<u64>>,
+    // bitmasks for chars that do not appear in the query
+    no_peq: Vec<u64>,
+    // positive and negative vertical deltas of the current column
+    pv: Vec<u64>,
+    mv: Vec<u64>
+}
+
+impl DistanceCalculator {
+    /// Instantiates a new calculator
+    ///
+    /// # Arguments
+    /// * `query` - the string that candidates will be compared with
+    ///
+    /// # Returns
+    /// * A new `DistanceCalculator` instance
+    ///
+    /// # Examples
+    /// ```
+    /// use levenshtein_lite::DistanceCalculator;
+    /// let mut calc = DistanceCalculator::new("abc");
+    /// assert!(calc.distance("abx") == 1);
+    /// assert!(calc.distance("axx") == 2);
+    /// ```
+    pub fn new(query: &str) -> Self {
+        let query_len = query.chars().count();
+        let blocks = query_len.div_ceil(WORD_BITS);
+        let mut ascii_peq = vec![0; 128 * blocks];
+        let mut peq: HashMap<char, Vec<u64>> = HashMap::new();
+        for (i, c) in query.chars().enumerate() {
+            let (block, bit) = (i / WORD_BITS, 1 << (i % WORD_BITS));
+            if c.is_ascii() {
+                ascii_peq[c as usize * blocks + block] |= bit;
+            } else {
+                peq.entry(c).or_insert_with(|| vec![0; blocks])[block] |= bit;
+            }
+        }
+        DistanceCalculator {
+            query_len,
+            blocks,
+            ascii_peq,
+            peq,
+            no_peq: vec![0; blocks],
+            pv: vec![0; blocks],
+            mv: vec![0; blocks]
+        }
+    }
+
+    /// Computes the Levenshtein distance between the query and a candidate
+    ///
+    /// # Arguments
+    /// * `candidate` - the string to compare with the query
+    ///
+    /// # Returns
+    /// * the same value as `levenshtein_distance(query, candidate)`
+    pub fn distance(&mut self, candidate: &str) -> i32 {
+        if self.query_len == 0 {
+            return candidate.chars().count() as i32;
+        }
+        let DistanceCalculator {
+            query_len, blocks, ascii_peq, peq, no_peq, pv, mv
+        } = self;
+        let blocks = *blocks;
+        // the last block may only be partially used by the query
+        let last_high = 1 << ((*query_len - 1) % WORD_BITS);
+
+        pv.fill(!0);
+        mv.fill(0);
+        let mut score = *query_len as i32;
+        for c in candidate.chars() {
+            let eq = if c.is_ascii() {
+                let start = c as usize * blocks;
+                &ascii_peq[start..start + blocks]
+            } else {
+                peq.get(&c).unwrap_or(no_peq)
+            };
+            // the first row of the matrix counts up from zero,
+            // so each column starts off one higher than the last
+            let mut h = 1;
+            for b in 0..blocks {
+                let high = if b == blocks - 1 { last_high } else { 1 << (WORD_BITS - 1) };
+                h = advance_block(&mut pv[b], &mut mv[b], eq[b], h, high);
+            }
+            score += h;
+        }
+        score
+    }
+
+    /// Computes the Levenshtein distances between the query and many candidates
+    ///
+    /// # Arguments
+    /// * `candidates` - the strings to compare with the query
+    ///
+    /// # Returns
+    /// * the distance to each candidate, in order
+    pub fn distance_many<'a, I>(&mut self, candidates: I) -> Vec<i32>
+    where
+        I: IntoIterator<Item = &'a str>
+    {
+        candidates
+            .into_iter()
+            .map(|candidate| self.distance(candidate))
+            .collect()
+    }
+}
+
+// Advances one 64 row block of the Myers bit-vectors by one column,
+// given the horizontal delta `hin` coming in from the block above,
+// and returns the horizontal delta at the block's `high` row
+fn advance_block(pv: &mut u64, mv: &mut u64, eq: u64, hin: i32, high: u64) -> i32 {
+    let (p, m) = (*pv, *mv);
+    let xv = eq | m;
+    let eq = if hin < 0 { eq | 1 } else { eq };
+    let xh = ((eq & p).wrapping_add(p) ^ p) | eq;
+    let mut ph = m | !(xh | p);
+    let mut mh = p & xh;
+
+    let hout = if ph & high != 0 {
+        1
+    } else if mh & high != 0 {
+        -1
+    } else {
+        0
+    };
+
+    ph <<= 1;
+    mh <<= 1;
+    if hin < 0 {
+        mh |= 1;
+    } else if hin > 0 {
+        ph |= 1;
+    }
+    *pv = mh | !(xv | ph);
+    *mv = ph & xv;
+    hout
+}
+
+/// Computes the Levenshtein distances between a query and many candidates
+///
+/// # Arguments
+/// * `query` - a string
+/// * `candidates` - the strings to compare with `query`
+///
+/// # Returns
+/// * `levenshtein_distance(query, candidate)` for each candidate, in order
+///
+/// # Examples
+/// ```
+/// use levenshtein_lite::levenshtein_distance_many;
+/// let distances = levenshtein_distance_many("abc", ["abx", "axx", ""]);
+/// assert!(distances == vec![1, 2, 3]);
+/// ```
+pub fn levenshtein_distance_many<'a, I>(query: &str, candidates: I) -> Vec<i32>
+where
+    I: IntoIterator<Item = &'a str>
+{
+    DistanceCalculator::new(query).distance_many(candidates)
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::levenshtein_distance;
+    use rand::Rng;
+
+    #[test]
+    fn basic_calculator() {
+        let mut calc = DistanceCalculator::new("");
+        assert!(calc.distance("") == 0);
+        assert!(calc.distance("abc") == 3);
+
+        let mut calc = DistanceCalculator::new("kitten");
+        assert!(calc.distance("") == 6);
+        assert!(calc.distance("sitting") == 3);
+        assert!(calc.distance("kitten") == 0);
+        assert!(calc.distance("日本kitten") == 2);
+
+        let mut calc = DistanceCalculator::new("日本語");
+        assert!(calc.distance("日語") == 1);
+        assert!(calc.distance("本日語") == 2);
+
+        // block boundaries
+        for len in [63, 64, 65, 128, 129] {
+            let query = "ab".repeat(len)[..len].to_string();
+            let swapped = query.replace('a', "b");
+            let mut calc = DistanceCalculator::new(&query);
+            assert!(calc.distance(&query) == 0);
+            assert!(calc.distance("") == len as i32);
+            assert!(calc.distance(&query[1..]) == 1);
+            assert!(calc.distance(&format!("{}x", query)) == 1);
+            assert!(calc.distance(&swapped) == levenshtein_distance(&query, &swapped));
+        }
+    }
+
+    #[test]
+    fn fuzz_calculator() {
+        let mut rng = rand::thread_rng();
+        let alphabet = ['a', 'b', 'c', 'é'];
+        let mut rand_string = |max_len: usize| {
+            (0..rng.gen_range(0..max_len))
+                .map(|_| alphabet[rng.gen_range(0..alphabet.len())])
+                .collect::<String>()
+        };
+
+        // cover queries that span one, two and three blocks
+        for _ in 0..100 {
+            let query = rand_string(160);
+            let candidates = (0..20)
+                .map(|_| rand_string(160))
+                .collect::<Vec<String>>();
+            let distances = levenshtein_distance_many(
+                &query,
+                candidates.iter().map(|c| c.as_str())
+            );
+            for (candidate, d) in candidates.iter().zip(distances) {
+                assert!(d == levenshtein_distance(&query, candidate));
+            }
+        }
+    }
+}
@@ -0,0 +1,250 @@
+use std::collections::HashMap;
+use crate::LevenshteinAutomata;
+
+const WORD_BITS: usize = 64;
+
+/// Checks strings against a source by simulating the Levenshtein NFA
+/// with bit-parallel rows (Wu–Manber)
+///
+/// Unlike `LevenshteinAutomata`, nothing is determinized, so instantiation
+/// is linear in the length of the source, and any distance is supported.
+/// Each check costs `O(input length * (max_distance + 1) * src length / 64)`.
+pub struct BitParallelMatcher {
+    len: usize,
+    max_distance: usize,
+    words: usize,
+    // for each char of the source, the positions where it occurs,
+    // shifted up by one so that bit `i + 1` means `src[i]`
+    masks: HashMap<char, Vec<u64>>,
+    empty_mask: Vec<u64>
+}
+
+impl BitParallelMatcher {
+    /// Instantiates a new matcher
+    ///
+    /// # Arguments
+    /// * `src` - the string that inputs will be compared with
+    /// * `max_distance` - the maximum acceptable Levenshtein Distance
+    ///
+    /// # Returns
+    /// * A new `BitParallelMatcher` instance
+    ///
+    /// # Examples
+    /// ```
+    /// use levenshtein_lite::BitParallelMatcher;
+    /// let matcher = BitParallelMatcher::new("Levenshtein", 5);
+    /// assert!(matcher.check("Einstein"));
+    /// assert!(!matcher.check("Frankenstein"));
+    /// ```
+    pub fn new(src: &str, max_distance: usize) -> Self {
+        let len = src.chars().count();
+        // bit `i` of a row stands for having matched the first `i` chars
+        let words = (len + 1).div_ceil(WORD_BITS);
+        let mut masks = HashMap::new();
+        for (i, c) in src.chars().enumerate() {
+            let mask = masks.entry(c).or_insert_with(|| vec![0u64; words]);
+            mask[(i + 1) / WORD_BITS] |= 1 << ((i + 1) % WORD_BITS);
+        }
+        BitParallelMatcher {
+            len,
+            max_distance,
+            words,
+            masks,
+            empty_mask: vec![0u64; words]
+        }
+    }
+
+    /// Checks an input string against the source string
+    ///
+    /// # Arguments
+    /// * `input` - the string to check against the source
+    ///
+    /// # Returns
+    /// * `true` if `LevenshteinDistance(src, input) <= max_distance`
+    pub fn check(&self, input: &str) -> bool {
+        // any input is within max(len, input len) of the source,
+        // so larger distances only cost more rows
+        let k = self.max_distance.min(self.len.max(input.chars().count()));
+        let w = self.words;
+        // row `d` holds the prefixes of src matched with at most `d` edits;
+        // initially, up to `d` chars of src can be deleted
+        let mut rows = vec![0u64; (k + 1) * w];
+        for d in 0..=k {
+            for i in 0..=d.min(self.len) {
+                rows[d * w + i / WORD_BITS] |= 1 << (i % WORD_BITS);
+            }
+        }
+        let last_word_mask = match (self.len + 1) % WORD_BITS {
+            0 => u64::MAX,
+            bits => (1 << bits) - 1
+        };
+
+        let mut old_prev = vec![0u64; w];
+        let mut old_cur = vec![0u64; w];
+        for c in input.chars() {
+            let mask = self.masks.get(&c).unwrap_or(&self.empty_mask);
+            for d in 0..=k {
+                let (done, rest) = rows.split_at_mut(d * w);
+                let row = &mut rest[..w];
+                old_cur.copy_from_slice(row);
+                for j in 0..w {
+                    // match: advance in src on an equal char
+                    let mut next = shift(&old_cur, j) & mask[j];
+                    if d > 0 {
+                        let new_prev = &done[(d - 1) * w..];
+                        // insertion: consume the input char only
+                        next |= old_prev[j];
+                        // substitution: consume both
+                        next |= shift(&old_prev, j);
+                        // deletion: skip a char of src
+                        next |= shift(new_prev, j);
+                    }
+                    row[j] = next;
+                }
+                row[w - 1] &= last_word_mask;
+                std::mem::swap(&mut old_prev, &mut old_cur);
+            }
+            if rows[k * w..].iter().all(|&word| word == 0) {
+                // every row is a subset of the last,
+                // so no prefix is matched anymore
+                return false;
+            }
+        }
+        rows[k * w + self.len / WORD_BITS] & (1 << (self.len % WORD_BITS)) != 0
+    }
+
+    /// Returns the maximum distance of the matcher
+    pub fn max_distance(&self) -> usize {
+        self.max_distance
+    }
+}
+
+// Word `j` of `row` shifted up by one bit, carrying from the word below
+fn shift(row: &[u64], j: usize) -> u64 {
+    let carry = if j > 0 { row[j - 1] >> (WORD_BITS - 1) } else { 0 };
+    (row[j] << 1) | carry
+}
+
+/// Checks strings against a source, with whichever of
+/// `LevenshteinAutomata` and `BitParallelMatcher` suits it best
+pub enum LevenshteinMatcher {
+    /// a determinized automata, for small distances
+    Dfa(LevenshteinAutomata),
+    /// a bit-parallel NFA simulation, for everything else
+    Nfa(BitParallelMatcher)
+}
+
+impl LevenshteinMatcher {
+    /// Instantiates a new matcher
+    ///
+    /// A DFA is built when it stays small, i.e. for small distances
+    /// and short sources; otherwise the NFA is simulated.
+    ///
+    /// # Arguments
+    /// * `src` - the string that inputs will be compared with
+    /// * `max_distance` - the maximum acceptable Levenshtein Distance
+    ///
+    /// # Returns
+    /// * A new `LevenshteinMatcher` instance
+    ///
+    /// # Examples
+    /// ```
+    /// use levenshtein_lite::LevenshteinMatcher;
+    /// let matcher = LevenshteinMatcher::new("abc", 1);
+    /// assert!(matches!(matcher, LevenshteinMatcher::Dfa(_)));
+    /// let matcher = LevenshteinMatcher::new("abcdefghijklmnopqrstuvwxyz", 10);
+    /// assert!(matches!(matcher, LevenshteinMatcher::Nfa(_)));
+    /// assert!(matcher.check("abcdefghijklmnop"));
+    /// ```
+    pub fn new(src: &str, max_distance: usize) -> Self {
+        let len = src.chars().count();
+        let dfa_fits = match max_distance {
+            0 | 1 => true,
+            2 => len <= 32,
+            3 => len <= 12,
+            _ => false
+        };
+        if dfa_fits {
+            LevenshteinMatcher::Dfa(LevenshteinAutomata::new(src, max_distance as i8))
+        } else {
+            LevenshteinMatcher::Nfa(BitParallelMatcher::new(src, max_distance))
+        }
+    }
+
+    /// Checks an input string against the source string
+    ///
+    /// # Arguments
+    /// * `input` - the string to check against the source
+    ///
+    /// # Returns
+    /// * `true` if `LevenshteinDistance(src, input) <= max_distance`
+    pub fn check(&self, input: &str) -> bool {
+        match self {
+            LevenshteinMatcher::Dfa(lda) => lda.check(input),
+            LevenshteinMatcher::Nfa(matcher) => matcher.check(input)
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::levenshtein_distance;
+    use rand::{Rng, SeedableRng, rngs::StdRng};
+
+    #[test]
+    fn nfa_agrees_with_distance() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let alphabet = ['a', 'b', 'c', 'é'];
+        let random_string = |rng: &mut StdRng, len: usize| {
+            (0..len).map(|_| alphabet[rng.gen_range(0..alphabet.len())]).collect::<String>()
+        };
+        // lengths either side of the word boundaries
+        for src_len in [0, 1, 5, 62, 63, 64, 65, 130] {
+            let src = random_string(&mut rng, src_len);
+            for _ in 0..20 {
+                let input_len = rng.gen_range(0..=src_len + 4);
+                let mut input = random_string(&mut rng, input_len);
+                if rng.gen_bool(0.5) && src_len > 0 {
+                    // mostly similar inputs, to land near the boundary
+                    let mut chars = src.chars().collect::<Vec<char>>();
+                    for _ in 0..rng.gen_range(0..=4) {
+                        let i = rng.gen_range(0..chars.len());
+                        chars[i] = 'x';
+                    }
+                    input = chars.into_iter().collect();
+                }
+                let distance = levenshtein_distance(&src, &input) as usize;
+                for k in [0, 1, 2, 3, 4, 8, 70] {
+                    let matcher = BitParallelMatcher::new(&src, k);
+                    assert!(matcher.check(&input) == (distance <= k), "{} {} {}", src, input, k);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn huge_distances() {
+        let matcher = BitParallelMatcher::new("abc", usize::MAX);
+        assert!(matcher.max_distance() == usize::MAX);
+        for input in ["", "abc", "xyz", "a much longer input than the source"] {
+            assert!(matcher.check(input));
+        }
+        let matcher = BitParallelMatcher::new("", usize::MAX);
+        assert!(matcher.check("") && matcher.check("abc"));
+    }
+
+    #[test]
+    fn matcher_selection() {
+        let src = "Levenshtein";
+        for k in 0..=8 {
+            let matcher = LevenshteinMatcher::new(src, k);
+            assert!(matches!(matcher, LevenshteinMatcher::Dfa(_)) == (k <= 3));
+            for input in ["Levenshtein", "Levenstein", "Lewenstein", "Einstein", "Frankenstein", ""] {
+                let expected = levenshtein_distance(src, input) as usize <= k;
+                assert!(matcher.check(input) == expected);
+            }
+        }
+    }
+}
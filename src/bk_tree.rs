@@ -0,0 +1,316 @@
+use crate::{levenshtein_distance, levenshtein_distance_bounded};
+
+struct BkNode {
+    term: String,
+    // removed terms stay in place as routing nodes,
+    // since their children hang off their distances
+    removed: bool,
+    // (distance to this term, node index), sorted by distance
+    children: Vec<(i32, usize)>
+}
+
+impl BkNode {
+    fn max_child_distance(&self) -> i32 {
+        self.children.last().map(|&(d, _)| d).unwrap_or(0)
+    }
+}
+
+/// A BK-tree over strings, using Levenshtein distance as its metric
+///
+/// Terms can be added and removed at any time. Queries only compare
+/// against the subtrees that the triangle inequality can't rule out.
+#[derive(Default)]
+pub struct BkTree {
+    nodes: Vec<BkNode>,
+    len: usize
+}
+
+impl BkTree {
+    /// Instantiates a new, empty tree
+    pub fn new() -> Self {
+        BkTree { nodes: vec![], len: 0 }
+    }
+
+    /// Returns the number of terms in the tree
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the tree holds no terms
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    // Finds the node holding `term`, removed or not
+    fn position(&self, term: &str) -> Option<usize> {
+        if self.nodes.is_empty() {
+            return None;
+        }
+        let mut idx = 0;
+        loop {
+            let node = &self.nodes[idx];
+            let d = levenshtein_distance(term, &node.term);
+            if d == 0 {
+                return Some(idx);
+            }
+            match node.children.binary_search_by_key(&d, |&(cd, _)| cd) {
+                Ok(i) => idx = node.children[i].1,
+                Err(_) => return None
+            }
+        }
+    }
+
+    /// Adds a term to the tree
+    ///
+    /// # Arguments
+    /// * `term` - the string to add
+    ///
+    /// # Returns
+    /// * `true` if the term was not already in the tree
+    ///
+    /// # Examples
+    /// ```
+    /// use levenshtein_lite::BkTree;
+    /// let mut tree = BkTree::new();
+    /// assert!(tree.insert("abc"));
+    /// assert!(!tree.insert("abc"));
+    /// assert!(tree.len() == 1);
+    /// ```
+    pub fn insert(&mut self, term: &str) -> bool {
+        let new_node = BkNode {
+            term: term.to_string(),
+            removed: false,
+            children: vec![]
+        };
+        if self.nodes.is_empty() {
+            self.nodes.push(new_node);
+            self.len += 1;
+            return true;
+        }
+        let mut idx = 0;
+        loop {
+            let d = levenshtein_distance(term, &self.nodes[idx].term);
+            if d == 0 {
+                let node = &mut self.nodes[idx];
+                if node.removed {
+                    node.removed = false;
+                    self.len += 1;
+                    return true;
+                }
+                return false;
+            }
+            match self.nodes[idx].children.binary_search_by_key(&d, |&(cd, _)| cd) {
+                Ok(i) => idx = self.nodes[idx].children[i].1,
+                Err(i) => {
+                    let new_idx = self.nodes.len();
+                    self.nodes.push(new_node);
+                    self.nodes[idx].children.insert(i, (d, new_idx));
+                    self.len += 1;
+                    return true;
+                }
+            }
+        }
+    }
+
+    /// Removes a term from the tree
+    ///
+    /// # Arguments
+    /// * `term` - the string to remove
+    ///
+    /// # Returns
+    /// * `true` if the term was in the tree
+    pub fn remove(&mut self, term: &str) -> bool {
+        match self.position(term) {
+            Some(idx) if !self.nodes[idx].removed => {
+                self.nodes[idx].removed = true;
+                self.len -= 1;
+                true
+            },
+            _ => false
+        }
+    }
+
+    /// Returns `true` if the term is in the tree
+    pub fn contains(&self, term: &str) -> bool {
+        self.position(term).is_some_and(|idx| !self.nodes[idx].removed)
+    }
+
+    /// Finds every term within a given distance of a query
+    ///
+    /// # Arguments
+    /// * `query` - the string to compare terms with
+    /// * `max_distance` - the maximum acceptable Levenshtein Distance
+    ///
+    /// # Returns
+    /// * `(term, distance)` pairs, sorted by distance, then term
+    ///
+    /// # Examples
+    /// ```
+    /// use levenshtein_lite::BkTree;
+    /// let mut tree = BkTree::new();
+    /// for term in ["book", "books", "cake", "boo", "cape", "cart"] {
+    ///     tree.insert(term);
+    /// }
+    /// assert!(tree.find("bo", 2) == vec![("boo", 1), ("book", 2)]);
+    /// ```
+    pub fn find(&self, query: &str, max_distance: i32) -> Vec<(&str, i32)> {
+        let mut found = vec![];
+        if self.nodes.is_empty() {
+            return found;
+        }
+        let mut stack = vec![0];
+        while let Some(idx) = stack.pop() {
+            let node = &self.nodes[idx];
+            // the exact distance only matters for picking children,
+            // which are at most this far away from the node
+            let bound = max_distance + node.max_child_distance();
+            let d = match levenshtein_distance_bounded(query, &node.term, bound) {
+                Some(d) => d,
+                None => continue
+            };
+            if d <= max_distance && !node.removed {
+                found.push((node.term.as_str(), d));
+            }
+            for &(cd, child) in node.children.iter() {
+                if (cd - d).abs() <= max_distance {
+                    stack.push(child);
+                }
+            }
+        }
+        found.sort_by(|a, b| (a.1, a.0).cmp(&(b.1, b.0)));
+        found
+    }
+
+    /// Finds the term closest to a query
+    ///
+    /// # Arguments
+    /// * `query` - the string to compare terms with
+    ///
+    /// # Returns
+    /// * the closest `(term, distance)` pair, preferring the
+    ///   lexicographically smallest term on ties,
+    ///   or `None` if the tree is empty
+    ///
+    /// # Examples
+    /// ```
+    /// use levenshtein_lite::BkTree;
+    /// let mut tree = BkTree::new();
+    /// for term in ["book", "books", "cake", "boo", "cape", "cart"] {
+    ///     tree.insert(term);
+    /// }
+    /// assert!(tree.nearest("cak") == Some(("cake", 1)));
+    /// ```
+    pub fn nearest(&self, query: &str) -> Option<(&str, i32)> {
+        let mut best: Option<(&str, i32)> = None;
+        if self.nodes.is_empty() {
+            return best;
+        }
+        let mut stack = vec![0];
+        while let Some(idx) = stack.pop() {
+            let node = &self.nodes[idx];
+            let d = match best {
+                Some((_, best_d)) => {
+                    let bound = best_d + node.max_child_distance();
+                    match levenshtein_distance_bounded(query, &node.term, bound) {
+                        Some(d) => d,
+                        None => continue
+                    }
+                },
+                None => levenshtein_distance(query, &node.term)
+            };
+            if !node.removed {
+                best = match best {
+                    Some((term, best_d)) if (best_d, term) <= (d, node.term.as_str()) => best,
+                    _ => Some((node.term.as_str(), d))
+                };
+            }
+            for &(cd, child) in node.children.iter() {
+                // ties are kept, so that the smallest term wins
+                if best.is_none_or(|(_, best_d)| (cd - d).abs() <= best_d) {
+                    stack.push(child);
+                }
+            }
+        }
+        best
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use rand::Rng;
+
+    #[test]
+    fn basic_tree() {
+        let mut tree = BkTree::new();
+        assert!(tree.is_empty());
+        assert!(tree.nearest("abc").is_none());
+        assert!(tree.find("abc", 3).is_empty());
+
+        for term in ["abc", "abd", "xyz", "ab", ""] {
+            assert!(tree.insert(term));
+        }
+        assert!(tree.len() == 5);
+        assert!(tree.contains("ab") && !tree.contains("a"));
+        assert!(tree.find("abc", 1) == vec![("abc", 0), ("ab", 1), ("abd", 1)]);
+
+        // the root can be removed and re-added
+        assert!(tree.remove("abc"));
+        assert!(!tree.remove("abc"));
+        assert!(!tree.contains("abc"));
+        assert!(tree.find("abc", 1) == vec![("ab", 1), ("abd", 1)]);
+        assert!(tree.nearest("abc") == Some(("ab", 1)));
+        assert!(tree.insert("abc"));
+        assert!(tree.nearest("abc") == Some(("abc", 0)));
+        assert!(tree.len() == 5);
+    }
+
+    #[test]
+    fn fuzz_tree() {
+        let mut rng = rand::thread_rng();
+        let rand_string = |rng: &mut rand::rngs::ThreadRng| {
+            (0..rng.gen_range(0..8))
+                .map(|_| ['a', 'b', 'c', 'é'][rng.gen_range(0..4)])
+                .collect::<String>()
+        };
+        for _ in 0..20 {
+            let mut tree = BkTree::new();
+            let mut terms: Vec<String> = vec![];
+            for _ in 0..200 {
+                let term = rand_string(&mut rng);
+                if rng.gen_range(0..4) == 0 {
+                    let expected = terms.contains(&term);
+                    assert!(tree.remove(&term) == expected);
+                    terms.retain(|t| *t != term);
+                } else {
+                    let expected = !terms.contains(&term);
+                    assert!(tree.insert(&term) == expected);
+                    if expected {
+                        terms.push(term);
+                    }
+                }
+            }
+            assert!(tree.len() == terms.len());
+
+            for _ in 0..50 {
+                let query = rand_string(&mut rng);
+                let max_distance = rng.gen_range(0..4);
+                let mut expected = terms
+                    .iter()
+                    .map(|t| (t.as_str(), levenshtein_distance(&query, t)))
+                    .filter(|&(_, d)| d <= max_distance)
+                    .collect::<Vec<(&str, i32)>>();
+                expected.sort_by(|a, b| (a.1, a.0).cmp(&(b.1, b.0)));
+                assert!(tree.find(&query, max_distance) == expected);
+
+                let nearest = terms
+                    .iter()
+                    .map(|t| (levenshtein_distance(&query, t), t.as_str()))
+                    .min()
+                    .map(|(d, t)| (t, d));
+                assert!(tree.nearest(&query) == nearest);
+            }
+        }
+    }
+}
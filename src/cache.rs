@@ -0,0 +1,273 @@
+use std::collections::{BTreeMap, HashMap};
+use std::ops::RangeInclusive;
+use std::sync::{Arc, Mutex, MutexGuard};
+use crate::{LevenshteinAutomata, MatchMode};
+
+type CacheKey = (String, RangeInclusive<i8>, MatchMode);
+
+/// A snapshot of the counters of an `AutomataCache`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct CacheStats {
+    /// lookups answered from the cache
+    pub hits: u64,
+    /// lookups that built a new automata
+    pub misses: u64,
+    /// automata dropped to make room for newer ones
+    pub evictions: u64,
+    /// automata currently cached
+    pub entries: usize
+}
+
+struct CacheEntry {
+    automata: Arc<LevenshteinAutomata>,
+    last_used: u64
+}
+
+#[derive(Default)]
+struct CacheInner {
+    entries: HashMap<CacheKey, CacheEntry>,
+    // the keys of `entries`, least recently used first
+    recency: BTreeMap<u64, CacheKey>,
+    tick: u64,
+    stats: CacheStats
+}
+
+impl CacheInner {
+    fn touch(&mut self, key: &CacheKey) -> Option<Arc<LevenshteinAutomata>> {
+        self.tick += 1;
+        let tick = self.tick;
+        let entry = self.entries.get_mut(key)?;
+        let key = self.recency.remove(&entry.last_used).unwrap();
+        entry.last_used = tick;
+        self.recency.insert(tick, key);
+        Some(entry.automata.clone())
+    }
+}
+
+/// A bounded, thread-safe cache of `LevenshteinAutomata`
+///
+/// Automata are keyed by their source, distances and match mode, and once the
+/// cache is full the least recently used automata is evicted. Automata are
+/// built without holding the cache's lock, so lookups from other threads
+/// aren't held up by a slow build.
+///
+/// # Examples
+/// ```
+/// use std::sync::Arc;
+/// use levenshtein_lite::AutomataCache;
+/// let cache = AutomataCache::new(100);
+/// let lda = cache.get("levenshtein", 2);
+/// assert!(lda.check("levenstein"));
+/// assert!(Arc::ptr_eq(&lda, &cache.get("levenshtein", 2)));
+/// assert!(cache.stats().hits == 1 && cache.stats().misses == 1);
+/// ```
+pub struct AutomataCache {
+    capacity: usize,
+    inner: Mutex<CacheInner>
+}
+
+impl AutomataCache {
+    /// Instantiates a new, empty cache
+    ///
+    /// # Arguments
+    /// * `capacity` - the maximum number of automata to keep
+    pub fn new(capacity: usize) -> Self {
+        AutomataCache {
+            capacity,
+            inner: Mutex::new(CacheInner::default())
+        }
+    }
+
+    /// Returns the automata for a source and distance,
+    /// building and caching it if needed
+    ///
+    /// # Arguments
+    /// * `src` - the string that inputs will be compared with
+    /// * `max_distance` - the maximum acceptable Levenshtein Distance
+    ///
+    /// # Returns
+    /// * a shared `LevenshteinAutomata`, equal to `LevenshteinAutomata::new(src, max_distance)`
+    pub fn get(&self, src: &str, max_distance: i8) -> Arc<LevenshteinAutomata> {
+        self.get_with(src, 0..=max_distance, MatchMode::Full)
+    }
+
+    /// Returns the automata for a source, range of distances and match mode,
+    /// building and caching it if needed
+    ///
+    /// The lower bound of `distances` only applies to `MatchMode::Full`;
+    /// as with `LevenshteinAutomata::with_match_mode`, the other modes
+    /// accept any distance up to the upper bound.
+    ///
+    /// # Arguments
+    /// * `src` - the string that inputs will be compared with
+    /// * `distances` - the acceptable Levenshtein Distances
+    /// * `mode` - which part of the input must match `src`
+    ///
+    /// # Returns
+    /// * a shared `LevenshteinAutomata`, equal to
+    ///   `LevenshteinAutomata::with_distance_range(src, distances)` in `MatchMode::Full`,
+    ///   or to `LevenshteinAutomata::with_match_mode(src, *distances.end(), mode)` otherwise
+    ///
+    /// # Examples
+    /// ```
+    /// use levenshtein_lite::{AutomataCache, MatchMode};
+    /// let cache = AutomataCache::new(100);
+    /// let typos = cache.get_with("colour", 1..=2, MatchMode::Full);
+    /// assert!(typos.check("color") && !typos.check("colour"));
+    /// let contains = cache.get_with("colour", 0..=1, MatchMode::Infix);
+    /// assert!(contains.check("the color red"));
+    /// assert!(cache.stats().misses == 2);
+    /// ```
+    pub fn get_with(
+        &self,
+        src: &str,
+        distances: RangeInclusive<i8>,
+        mode: MatchMode
+    ) -> Arc<LevenshteinAutomata> {
+        let distances = match mode {
+            MatchMode::Full => distances,
+            // only the upper bound applies, so equal automata share a key
+            _ => 0..=*distances.end()
+        };
+        let key = (src.to_string(), distances, mode);
+        {
+            let mut inner = self.lock();
+            if let Some(automata) = inner.touch(&key) {
+                inner.stats.hits += 1;
+                return automata;
+            }
+            inner.stats.misses += 1;
+        }
+
+        let automata = Arc::new(match mode {
+            MatchMode::Full => LevenshteinAutomata::with_distance_range(src, key.1.clone()),
+            _ => LevenshteinAutomata::with_match_mode(src, *key.1.end(), mode)
+        });
+        let mut inner = self.lock();
+        if let Some(existing) = inner.touch(&key) {
+            // another thread built it in the meantime
+            return existing;
+        }
+        if self.capacity == 0 {
+            return automata;
+        }
+        while inner.entries.len() >= self.capacity {
+            let (_, oldest) = inner.recency.pop_first().unwrap();
+            inner.entries.remove(&oldest);
+            inner.stats.evictions += 1;
+        }
+        // the failed lookup above took a fresh tick
+        let last_used = inner.tick;
+        inner.recency.insert(last_used, key.clone());
+        inner.entries.insert(key, CacheEntry {
+            automata: automata.clone(),
+            last_used
+        });
+        automata
+    }
+
+    /// Returns the counters of the cache
+    pub fn stats(&self) -> CacheStats {
+        let inner = self.lock();
+        CacheStats {
+            entries: inner.entries.len(),
+            ..inner.stats
+        }
+    }
+
+    /// Returns the maximum number of automata the cache keeps
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Returns the number of automata currently cached
+    pub fn len(&self) -> usize {
+        self.lock().entries.len()
+    }
+
+    /// Returns `true` if no automata are cached
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Drops every cached automata, keeping the counters
+    pub fn clear(&self) {
+        let mut inner = self.lock();
+        inner.entries.clear();
+        inner.recency.clear();
+    }
+
+    fn lock(&self) -> MutexGuard<'_, CacheInner> {
+        // the cache is left consistent between statements,
+        // so a panic elsewhere while holding the lock can't corrupt it
+        self.inner.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::thread;
+
+    #[test]
+    fn lru_eviction() {
+        let cache = AutomataCache::new(2);
+        let abc = cache.get("abc", 1);
+        assert!(*abc == LevenshteinAutomata::new("abc", 1));
+        cache.get("abd", 1);
+        // refresh abc, so abd is the least recently used
+        assert!(Arc::ptr_eq(&abc, &cache.get("abc", 1)));
+        cache.get("abc", 2);
+        assert!(cache.stats() == CacheStats { hits: 1, misses: 3, evictions: 1, entries: 2 });
+        assert!(Arc::ptr_eq(&abc, &cache.get("abc", 1)));
+        // abd was evicted, so is built again, evicting abc at distance 2
+        cache.get("abd", 1);
+        assert!(cache.stats() == CacheStats { hits: 2, misses: 4, evictions: 2, entries: 2 });
+
+        cache.clear();
+        assert!(cache.is_empty() && cache.stats().misses == 4);
+
+        // distances and match modes are part of the key
+        let cache = AutomataCache::new(8);
+        let plain = cache.get("abc", 2);
+        let typos = cache.get_with("abc", 1..=2, MatchMode::Full);
+        assert!(*typos == LevenshteinAutomata::with_distance_range("abc", 1..=2));
+        assert!(!Arc::ptr_eq(&plain, &typos) && plain.check("abc") && !typos.check("abc"));
+        assert!(Arc::ptr_eq(&plain, &cache.get_with("abc", 0..=2, MatchMode::Full)));
+        let suffix = cache.get_with("abc", 0..=1, MatchMode::Suffix);
+        let infix = cache.get_with("abc", 0..=1, MatchMode::Infix);
+        assert!(*suffix == LevenshteinAutomata::with_match_mode("abc", 1, MatchMode::Suffix));
+        assert!(*infix == LevenshteinAutomata::with_match_mode("abc", 1, MatchMode::Infix));
+        assert!(suffix.check("xxabc") && !suffix.check("abcxx") && infix.check("xabcx"));
+        // the lower bound doesn't apply outside of full matches
+        assert!(Arc::ptr_eq(&infix, &cache.get_with("abc", 1..=1, MatchMode::Infix)));
+        assert!(cache.stats() == CacheStats { hits: 2, misses: 4, evictions: 0, entries: 4 });
+
+        let uncached = AutomataCache::new(0);
+        assert!(uncached.get("abc", 1).check("abx"));
+        assert!(uncached.is_empty() && uncached.stats().misses == 1);
+    }
+
+    #[test]
+    fn shared_across_threads() {
+        let cache = AutomataCache::new(8);
+        let words = ["apple", "banana", "cherry", "damson"];
+        thread::scope(|scope| {
+            for t in 0..4 {
+                let cache = &cache;
+                scope.spawn(move || {
+                    for i in 0..50 {
+                        let word = words[(i + t) % words.len()];
+                        assert!(cache.get(word, 1).check(word));
+                    }
+                });
+            }
+        });
+        let stats = cache.stats();
+        assert!(stats.hits + stats.misses == 200);
+        assert!(stats.entries == 4 && stats.evictions == 0);
+        // racing threads may each build a word once
+        assert!(stats.misses >= 4 && stats.misses <= 16);
+    }
+}
@@ -0,0 +1,319 @@
+use std::collections::{HashMap, VecDeque};
+use std::hash::Hash;
+use crate::{LevenshteinAutomata, Label};
+
+type Edges<T> = Vec<(Label<T>, usize)>;
+
+/// A deterministic automata, for combining `LevenshteinAutomata`
+///
+/// Like `LevenshteinAutomata`, each state has edges labelled with a
+/// specific symbol, and at most one `Label::Any` edge taken by every
+/// other symbol. A symbol with neither rejects the input.
+///
+/// # Examples
+/// ```
+/// use levenshtein_lite::{Dfa, LevenshteinAutomata};
+/// let near_x = Dfa::from(&LevenshteinAutomata::new("color", 2));
+/// let exactly_x = Dfa::from(&LevenshteinAutomata::new("color", 0));
+/// let typos = near_x.difference(&exactly_x);
+/// assert!(typos.check("colour"));
+/// assert!(!typos.check("color"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Dfa<T = char> {
+    // the sorted outgoing edges of each state; state 0 is the start
+    edges: Vec<Edges<T>>,
+    accepting: Vec<bool>
+}
+
+impl<T: Eq + Hash + Ord + Clone> From<&LevenshteinAutomata<T>> for Dfa<T> {
+    fn from(lda: &LevenshteinAutomata<T>) -> Self {
+        Dfa {
+            edges: lda.states()
+                .map(|state| {
+                    let mut edges = lda.edges(state).collect::<Edges<T>>();
+                    edges.sort();
+                    edges
+                })
+                .collect(),
+            accepting: lda.states().map(|state| lda.is_accepting(state)).collect()
+        }
+    }
+}
+
+impl Dfa {
+    /// Checks an input string against the automata
+    ///
+    /// # Arguments
+    /// * `input` - the string to check
+    ///
+    /// # Returns
+    /// * `true` if the automata accepts `input`
+    pub fn check(&self, input: &str) -> bool {
+        self.check_symbols(input.chars())
+    }
+
+    /// Finds a shortest string the automata accepts
+    ///
+    /// Where any char would do, the first char from `'a'` onwards
+    /// without an edge of its own is used.
+    ///
+    /// # Returns
+    /// * the first accepted string in shortest, then edge, order,
+    ///   or `None` if the automata accepts nothing
+    ///
+    /// # Examples
+    /// ```
+    /// use levenshtein_lite::{Dfa, LevenshteinAutomata};
+    /// let color = Dfa::from(&LevenshteinAutomata::new("color", 1));
+    /// let colour = Dfa::from(&LevenshteinAutomata::new("colour", 1));
+    /// assert!(color.intersection(&colour).shortest_accepted_string() == Some("color".to_string()));
+    /// ```
+    pub fn shortest_accepted_string(&self) -> Option<String> {
+        let path = self.shortest_path()?;
+        let mut accepted = String::new();
+        let mut state = 0;
+        for edge_idx in path {
+            let (label, next) = &self.edges[state][edge_idx];
+            let c = match *label {
+                Label::Char(c) => c,
+                Label::Any => {
+                    let taken = |c: &char| self.edges[state].iter().any(|(l, _)| *l == Label::Char(*c));
+                    ('a'..=char::MAX).find(|c| !taken(c)).unwrap()
+                }
+            };
+            accepted.push(c);
+            state = *next;
+        }
+        Some(accepted)
+    }
+}
+
+impl<T: Eq + Hash + Ord + Clone> Dfa<T> {
+    /// Checks an input sequence against the automata
+    ///
+    /// # Arguments
+    /// * `input` - the symbols to check
+    ///
+    /// # Returns
+    /// * `true` if the automata accepts `input`
+    pub fn check_symbols<I: IntoIterator<Item = T>>(&self, input: I) -> bool {
+        let mut state = 0;
+        for c in input {
+            match self.follow(state, &Label::Char(c)) {
+                Some(next) => state = next,
+                None => return false
+            }
+        }
+        self.accepting[state]
+    }
+
+    /// Returns the number of states in the automata
+    pub fn num_states(&self) -> usize {
+        self.edges.len()
+    }
+
+    /// Builds the automata accepting what both automata accept
+    pub fn intersection(&self, other: &Self) -> Self {
+        self.product(other, false)
+    }
+
+    /// Builds the automata accepting what either automata accepts
+    pub fn union(&self, other: &Self) -> Self {
+        self.product(other, true)
+    }
+
+    /// Builds the automata accepting what this automata accepts,
+    /// but `other` doesn't
+    pub fn difference(&self, other: &Self) -> Self {
+        self.intersection(&other.complement())
+    }
+
+    /// Builds the automata accepting exactly what this automata rejects
+    pub fn complement(&self) -> Self {
+        // inputs that would be rejected for lack of an edge are
+        // sent to a new sink state instead, which accepts
+        let sink = self.edges.len();
+        let mut edges = self.edges.clone();
+        for state_edges in edges.iter_mut() {
+            if !state_edges.iter().any(|(label, _)| *label == Label::Any) {
+                // ANY sorts after every specific symbol
+                state_edges.push((Label::Any, sink));
+            }
+        }
+        edges.push(vec![(Label::Any, sink)]);
+        let mut accepting = self.accepting.iter().map(|&acc| !acc).collect::<Vec<bool>>();
+        accepting.push(true);
+        Dfa { edges, accepting }
+    }
+
+    /// Returns `true` if the automata accepts no input at all
+    pub fn is_empty(&self) -> bool {
+        self.shortest_path().is_none()
+    }
+
+    /// Finds a shortest sequence the automata accepts
+    ///
+    /// # Returns
+    /// * the labels of the edges taken by the first accepted sequence in
+    ///   shortest, then edge, order, where `Label::Any` stands for any symbol
+    ///   without an edge of its own; or `None` if the automata accepts nothing
+    pub fn shortest_accepted(&self) -> Option<Vec<Label<T>>> {
+        let path = self.shortest_path()?;
+        let mut labels = vec![];
+        let mut state = 0;
+        for edge_idx in path {
+            let (label, next) = &self.edges[state][edge_idx];
+            labels.push(label.clone());
+            state = *next;
+        }
+        Some(labels)
+    }
+
+    // Finds the edges, by index, leading to the nearest accepting state
+    fn shortest_path(&self) -> Option<Vec<usize>> {
+        // the state and edge each state was first reached from
+        let mut reached_from: Vec<Option<(usize, usize)>> = vec![None; self.edges.len()];
+        let mut queue = VecDeque::from([0]);
+        let mut seen = vec![false; self.edges.len()];
+        seen[0] = true;
+        while let Some(state) = queue.pop_front() {
+            if self.accepting[state] {
+                let mut path = vec![];
+                let mut at = state;
+                while let Some((prev, edge_idx)) = reached_from[at] {
+                    path.push(edge_idx);
+                    at = prev;
+                }
+                path.reverse();
+                return Some(path);
+            }
+            for (edge_idx, &(_, next)) in self.edges[state].iter().enumerate() {
+                if !seen[next] {
+                    seen[next] = true;
+                    reached_from[next] = Some((state, edge_idx));
+                    queue.push_back(next);
+                }
+            }
+        }
+        None
+    }
+
+    // Follows the edge a label leads along; a specific symbol
+    // falls back to ANY if it has no edge of its own
+    fn follow(&self, state: usize, label: &Label<T>) -> Option<usize> {
+        let mut any_target = None;
+        for (edge_label, next) in &self.edges[state] {
+            if edge_label == label {
+                return Some(*next);
+            }
+            if *edge_label == Label::Any {
+                any_target = Some(*next);
+            }
+        }
+        any_target
+    }
+
+    fn product(&self, other: &Self, union: bool) -> Self {
+        type Pair = (Option<usize>, Option<usize>);
+        let alive = |(a, b): Pair| if union { a.is_some() || b.is_some() } else { a.is_some() && b.is_some() };
+
+        let mut edges = vec![];
+        let mut accepting = vec![];
+        // states are numbered in the order they are first reached
+        let start = (Some(0), Some(0));
+        let mut placed = HashMap::from([(start, 0)]);
+        let mut queue = VecDeque::from([start]);
+        while let Some((a, b)) = queue.pop_front() {
+            let a_accepting = a.is_some_and(|a| self.accepting[a]);
+            let b_accepting = b.is_some_and(|b| other.accepting[b]);
+            accepting.push(if union { a_accepting || b_accepting } else { a_accepting && b_accepting });
+
+            // a symbol with an edge on one side only takes ANY on the other,
+            // and symbols with no edge on either side take ANY on both
+            let mut labels = vec![Label::Any];
+            for (state, dfa) in [(a, self), (b, other)] {
+                if let Some(state) = state {
+                    labels.extend(dfa.edges[state].iter().map(|(label, _)| label.clone()));
+                }
+            }
+            labels.sort();
+            labels.dedup();
+
+            let mut state_edges = vec![];
+            for label in labels {
+                let next = (
+                    a.and_then(|a| self.follow(a, &label)),
+                    b.and_then(|b| other.follow(b, &label))
+                );
+                if !alive(next) {
+                    continue;
+                }
+                let next_idx = match placed.get(&next) {
+                    Some(&idx) => idx,
+                    None => {
+                        let idx = placed.len();
+                        placed.insert(next, idx);
+                        queue.push_back(next);
+                        idx
+                    }
+                };
+                state_edges.push((label, next_idx));
+            }
+            edges.push(state_edges);
+        }
+        Dfa { edges, accepting }
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::levenshtein_distance;
+    use crate::verify::all_strings;
+
+    #[test]
+    fn boolean_combinations() {
+        let within = |src: &str, d: i8| Dfa::from(&LevenshteinAutomata::new(src, d));
+        let pairs = [("abc", 1, "abd", 1), ("ab", 2, "ab", 0), ("a", 1, "bb", 1), ("", 1, "abc", 1)];
+        for (x, dx, y, dy) in pairs {
+            let (a, b) = (within(x, dx), within(y, dy));
+            let (and, or, not, minus) = (a.intersection(&b), a.union(&b), a.complement(), a.difference(&b));
+            // 'z' only ever follows an ANY edge
+            for input in all_strings(&['a', 'b', 'c', 'd', 'z'], 5) {
+                let in_a = levenshtein_distance(x, &input) <= dx as i32;
+                let in_b = levenshtein_distance(y, &input) <= dy as i32;
+                assert!(and.check(&input) == (in_a && in_b), "{} {} {}", x, y, input);
+                assert!(or.check(&input) == (in_a || in_b));
+                assert!(not.check(&input) != in_a);
+                assert!(minus.check(&input) == (in_a && !in_b));
+            }
+        }
+    }
+
+    #[test]
+    fn emptiness_and_witnesses() {
+        let within = |src: &str, d: i8| Dfa::from(&LevenshteinAutomata::new(src, d));
+        assert!(within("abc", 1).intersection(&within("xyz", 1)).is_empty());
+        assert!(within("abc", 1).intersection(&within("xyz", 1)).shortest_accepted().is_none());
+        assert!(!within("abc", 2).intersection(&within("xyz", 2)).is_empty());
+        assert!(within("abc", 1).difference(&within("abc", 2)).is_empty());
+
+        let typos = within("ab", 1).difference(&within("ab", 0));
+        assert!(typos.shortest_accepted() == Some(vec![Label::Char('a')]));
+        assert!(typos.shortest_accepted_string() == Some("a".to_string()));
+        // every string of up to 2 chars is within 2 of "ab"
+        let far = within("ab", 2).complement();
+        let witness = far.shortest_accepted_string().unwrap();
+        assert!(witness.chars().count() == 3 && levenshtein_distance("ab", &witness) == 3);
+        assert!(far.shortest_accepted().unwrap().len() == 3);
+        assert!(within("", 0).shortest_accepted_string() == Some(String::new()));
+
+        // any symbol type
+        let words = Dfa::from(&LevenshteinAutomata::from_symbols(["red", "fox"], 1));
+        let witness = words.intersection(&Dfa::from(&LevenshteinAutomata::from_symbols(["fox"], 0)));
+        assert!(witness.shortest_accepted() == Some(vec![Label::Char("fox")]));
+        assert!(witness.check_symbols(["fox"]) && !witness.check_symbols(["red"]));
+    }
+}
@@ -0,0 +1,100 @@
+use crate::{Fuzziness, LevenshteinAutomata, levenshtein_distance};
+
+/// Finds the candidate closest to a mistyped keyword
+///
+/// The maximum distance is derived from the input length with
+/// `Fuzziness::AUTO`, so very short inputs must match exactly.
+///
+/// # Arguments
+/// * `input` - the keyword the user typed
+/// * `candidates` - the valid keywords
+///
+/// # Returns
+/// * the first of the closest candidates, or `None` if none are close enough
+///
+/// # Examples
+/// ```
+/// use levenshtein_lite::did_you_mean::suggest;
+/// let commands = ["build", "bench", "check", "clean"];
+/// assert!(suggest("buld", commands) == Some("build"));
+/// assert!(suggest("deploy", commands) == None);
+/// ```
+pub fn suggest<'a, I>(input: &str, candidates: I) -> Option<&'a str>
+where
+    I: IntoIterator<Item = &'a str>
+{
+    suggest_all(input, candidates).into_iter().next()
+}
+
+/// Finds every candidate close to a mistyped keyword
+///
+/// # Arguments
+/// * `input` - the keyword the user typed
+/// * `candidates` - the valid keywords
+///
+/// # Returns
+/// * the candidates within `Fuzziness::AUTO` of `input`, closest first;
+///   candidates at the same distance keep their input order
+///
+/// # Examples
+/// ```
+/// use levenshtein_lite::did_you_mean::suggest_all;
+/// let options = ["--verbose", "--version", "--quiet"];
+/// assert!(suggest_all("--verison", options) == vec!["--version"]);
+/// ```
+pub fn suggest_all<'a, I>(input: &str, candidates: I) -> Vec<&'a str>
+where
+    I: IntoIterator<Item = &'a str>
+{
+    suggest_all_with(input, candidates, Fuzziness::AUTO)
+}
+
+/// Finds every candidate close to a mistyped keyword,
+/// with a custom maximum distance
+///
+/// # Arguments
+/// * `input` - the keyword the user typed
+/// * `candidates` - the valid keywords
+/// * `fuzziness` - how to derive the maximum distance from `input`
+///
+/// # Returns
+/// * the candidates close enough to `input`, closest first;
+///   candidates at the same distance keep their input order
+pub fn suggest_all_with<'a, I>(input: &str, candidates: I, fuzziness: Fuzziness) -> Vec<&'a str>
+where
+    I: IntoIterator<Item = &'a str>
+{
+    let lda = LevenshteinAutomata::with_fuzziness(input, fuzziness);
+    let mut found = candidates
+        .into_iter()
+        .filter(|candidate| lda.check(candidate))
+        .map(|candidate| (levenshtein_distance(input, candidate), candidate))
+        .collect::<Vec<(i32, &str)>>();
+    // a stable sort keeps the input order among equals
+    found.sort_by_key(|&(d, _)| d);
+    found.into_iter().map(|(_, candidate)| candidate).collect()
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn basic_suggestions() {
+        let keywords = ["warning", "warn", "error", "info", "debug", "trace"];
+        assert!(suggest("warnnig", keywords) == Some("warning"));
+        assert!(suggest("eror", keywords) == Some("error"));
+        assert!(suggest("warn", keywords) == Some("warn"));
+        assert!(suggest("", keywords).is_none());
+        assert!(suggest("zzz", []).is_none());
+
+        // too short for any edits under AUTO
+        assert!(suggest("ib", ["in", "if"]).is_none());
+        assert!(suggest_all_with("ib", ["in", "if"], Fuzziness::Fixed(1)) == vec!["in", "if"]);
+
+        // closest first, ties keep their input order
+        assert!(suggest_all("bar", ["bare", "baz", "bar", "car"]) == vec!["bar", "bare", "baz", "car"]);
+        assert!(suggest_all("bar", ["car", "baz", "bare"]) == vec!["car", "baz", "bare"]);
+    }
+}
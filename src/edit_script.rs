@@ -0,0 +1,215 @@
+use std::cmp::min;
+
+/// A single step of an edit script that transforms `a` into `b`
+///
+/// `a_idx` and `b_idx` are char (not byte) positions. For an `Insert`,
+/// `a_idx` is the position in `a` before which the char is inserted;
+/// for a `Delete`, `b_idx` is the position in `b` at which the char
+/// would have appeared.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EditOp {
+    Match { a_idx: usize, b_idx: usize },
+    Substitute { a_idx: usize, b_idx: usize },
+    Insert { a_idx: usize, b_idx: usize },
+    Delete { a_idx: usize, b_idx: usize }
+}
+
+impl EditOp {
+    /// Returns the cost of the operation, i.e. `0` for a `Match`
+    /// and `1` for everything else
+    pub fn cost(&self) -> i32 {
+        match self {
+            EditOp::Match { .. } => 0,
+            _ => 1
+        }
+    }
+}
+
+/// Computes an optimal sequence of edit operations that transforms `a` into `b`
+///
+/// Ties between equally cheap scripts are broken deterministically:
+/// walking back from the end of both strings, a match or substitution
+/// is preferred over a deletion, and a deletion over an insertion.
+///
+/// # Arguments
+/// * `a` - the source string
+/// * `b` - the target string
+///
+/// # Returns
+/// * the edit operations, in order, covering every char of `a` and `b`;
+///   the number of non-`Match` operations equals `levenshtein_distance(a, b)`
+///
+/// # Examples
+/// ```
+/// use levenshtein_lite::{edit_script, EditOp};
+/// let ops = edit_script("abc", "axcd");
+/// assert_eq!(ops, vec![
+///     EditOp::Match { a_idx: 0, b_idx: 0 },
+///     EditOp::Substitute { a_idx: 1, b_idx: 1 },
+///     EditOp::Match { a_idx: 2, b_idx: 2 },
+///     EditOp::Insert { a_idx: 3, b_idx: 3 },
+/// ]);
+/// ```
+pub fn edit_script(a: &str, b: &str) -> Vec<EditOp> {
+    let a = a.chars().collect::<Vec<char>>();
+    let b = b.chars().collect::<Vec<char>>();
+    let cols = b.len() + 1;
+
+    // full (a.len() + 1) x (b.len() + 1) matrix, since we need to walk it back
+    let mut dist = vec![0; (a.len() + 1) * cols];
+    for (ai, row) in dist.chunks_mut(cols).enumerate() {
+        row[0] = ai as i32;
+    }
+    for (bi, cell) in dist[..cols].iter_mut().enumerate() {
+        *cell = bi as i32;
+    }
+    for ai in 1..a.len() + 1 {
+        for bi in 1..cols {
+            let sub_cost = if a[ai - 1] == b[bi - 1] { 0 } else { 1 };
+            let diag = dist[(ai - 1) * cols + bi - 1] + sub_cost;
+            let del = dist[(ai - 1) * cols + bi] + 1;
+            let ins = dist[ai * cols + bi - 1] + 1;
+            dist[ai * cols + bi] = min(diag, min(del, ins));
+        }
+    }
+
+    let mut ops = vec![];
+    let (mut ai, mut bi) = (a.len(), b.len());
+    while ai > 0 || bi > 0 {
+        let here = dist[ai * cols + bi];
+        if ai > 0 && bi > 0 {
+            let matched = a[ai - 1] == b[bi - 1];
+            let sub_cost = if matched { 0 } else { 1 };
+            if dist[(ai - 1) * cols + bi - 1] + sub_cost == here {
+                ai -= 1;
+                bi -= 1;
+                ops.push(if matched {
+                    EditOp::Match { a_idx: ai, b_idx: bi }
+                } else {
+                    EditOp::Substitute { a_idx: ai, b_idx: bi }
+                });
+                continue;
+            }
+        }
+        if ai > 0 && dist[(ai - 1) * cols + bi] + 1 == here {
+            ai -= 1;
+            ops.push(EditOp::Delete { a_idx: ai, b_idx: bi });
+        } else {
+            bi -= 1;
+            ops.push(EditOp::Insert { a_idx: ai, b_idx: bi });
+        }
+    }
+    ops.reverse();
+    ops
+}
+
+/// Aligns two strings according to `edit_script(a, b)`
+///
+/// # Arguments
+/// * `a` - the source string
+/// * `b` - the target string
+///
+/// # Returns
+/// * one pair per edit operation; `None` marks a gap,
+///   i.e. `(None, Some(c))` is an insertion and `(Some(c), None)` a deletion
+///
+/// # Examples
+/// ```
+/// use levenshtein_lite::alignment;
+/// let pairs = alignment("abc", "ac");
+/// assert_eq!(pairs, vec![
+///     (Some('a'), Some('a')),
+///     (Some('b'), None),
+///     (Some('c'), Some('c')),
+/// ]);
+/// ```
+pub fn alignment(a: &str, b: &str) -> Vec<(Option<char>, Option<char>)> {
+    let a_chars = a.chars().collect::<Vec<char>>();
+    let b_chars = b.chars().collect::<Vec<char>>();
+    edit_script(a, b)
+        .into_iter()
+        .map(|op| match op {
+            EditOp::Match { a_idx, b_idx } | EditOp::Substitute { a_idx, b_idx } => {
+                (Some(a_chars[a_idx]), Some(b_chars[b_idx]))
+            },
+            EditOp::Insert { b_idx, .. } => (None, Some(b_chars[b_idx])),
+            EditOp::Delete { a_idx, .. } => (Some(a_chars[a_idx]), None)
+        })
+        .collect()
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::levenshtein_distance;
+    use rand::Rng;
+
+    fn apply(a: &str, ops: &[EditOp], b: &str) -> String {
+        let a = a.chars().collect::<Vec<char>>();
+        let b = b.chars().collect::<Vec<char>>();
+        let mut out = String::new();
+        for op in ops {
+            match *op {
+                EditOp::Match { a_idx, b_idx } => {
+                    assert!(a[a_idx] == b[b_idx]);
+                    out.push(a[a_idx]);
+                },
+                EditOp::Substitute { a_idx, b_idx } => {
+                    assert!(a[a_idx] != b[b_idx]);
+                    out.push(b[b_idx]);
+                },
+                EditOp::Insert { b_idx, .. } => out.push(b[b_idx]),
+                EditOp::Delete { .. } => ()
+            }
+        }
+        out
+    }
+
+    #[test]
+    fn basic_script() {
+        assert!(edit_script("", "").is_empty());
+        assert_eq!(edit_script("", "ab"), vec![
+            EditOp::Insert { a_idx: 0, b_idx: 0 },
+            EditOp::Insert { a_idx: 0, b_idx: 1 },
+        ]);
+        assert_eq!(edit_script("ab", ""), vec![
+            EditOp::Delete { a_idx: 0, b_idx: 0 },
+            EditOp::Delete { a_idx: 1, b_idx: 0 },
+        ]);
+        // tie-break: the trailing 'a' is matched, the leading one deleted
+        assert_eq!(edit_script("aa", "a"), vec![
+            EditOp::Delete { a_idx: 0, b_idx: 0 },
+            EditOp::Match { a_idx: 1, b_idx: 0 },
+        ]);
+        assert_eq!(alignment("kitten", "sitting"), vec![
+            (Some('k'), Some('s')),
+            (Some('i'), Some('i')),
+            (Some('t'), Some('t')),
+            (Some('t'), Some('t')),
+            (Some('e'), Some('i')),
+            (Some('n'), Some('n')),
+            (None, Some('g')),
+        ]);
+    }
+
+    #[test]
+    fn fuzz_script() {
+        let mut rng = rand::thread_rng();
+        let alphabet = ['a', 'b', 'c', 'é'];
+        let mut rand_string = |len: usize| {
+            (0..len)
+                .map(|_| alphabet[rng.gen_range(0..alphabet.len())])
+                .collect::<String>()
+        };
+        for _ in 0..2000 {
+            let a = rand_string(7);
+            let b = rand_string(7);
+            let ops = edit_script(&a, &b);
+            let cost = ops.iter().map(|op| op.cost()).sum::<i32>();
+            assert!(cost == levenshtein_distance(&a, &b));
+            assert!(apply(&a, &ops, &b) == b);
+            assert!(ops == edit_script(&a, &b));
+        }
+    }
+}
@@ -0,0 +1,119 @@
+// the largest distance `Fuzziness::Ratio` derives; each extra edit makes
+// the eager automata around ten times larger and slower to build
+const MAX_RATIO_DISTANCE: i8 = 3;
+
+/// Describes how the `max_distance` of an automata is derived from its source
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Fuzziness {
+    /// A fixed maximum distance, regardless of the source length
+    Fixed(i8),
+    /// Elasticsearch-style length breakpoints: sources shorter than `low`
+    /// chars must match exactly, sources shorter than `high` chars allow
+    /// one edit, and longer sources allow two edits
+    Auto { low: usize, high: usize },
+    /// A minimum similarity in `[0, 1]`, measured against the source length
+    /// only: the maximum distance is `floor((1 - ratio) * len(src))`. Unlike
+    /// `normalized_levenshtein`, which divides by the longer of both strings,
+    /// inputs longer than the source aren't granted any extra edits.
+    /// The distance is capped at 3, as larger ones make the automata too
+    /// costly to build; long sources needing more edits are better served
+    /// by a `LevenshteinMatcher`. A NaN ratio is treated as `1.0`
+    Ratio(f64)
+}
+
+impl Fuzziness {
+    /// The breakpoints Elasticsearch uses for `fuzziness: AUTO`
+    pub const AUTO: Fuzziness = Fuzziness::Auto { low: 3, high: 6 };
+
+    /// Derives the maximum distance for a given source
+    ///
+    /// # Arguments
+    /// * `src` - the string that inputs will be compared with
+    ///
+    /// # Returns
+    /// * the maximum acceptable Levenshtein Distance for `src`
+    ///
+    /// # Examples
+    /// ```
+    /// use levenshtein_lite::Fuzziness;
+    /// assert!(Fuzziness::AUTO.max_distance("ab") == 0);
+    /// assert!(Fuzziness::AUTO.max_distance("abcd") == 1);
+    /// assert!(Fuzziness::AUTO.max_distance("abcdefg") == 2);
+    /// assert!(Fuzziness::Ratio(0.8).max_distance("abcdefghij") == 2);
+    /// ```
+    pub fn max_distance(&self, src: &str) -> i8 {
+        let len = src.chars().count();
+        match *self {
+            Fuzziness::Fixed(d) => d,
+            Fuzziness::Auto { low, high } => {
+                if len < low {
+                    0
+                } else if len < high {
+                    1
+                } else {
+                    2
+                }
+            },
+            Fuzziness::Ratio(ratio) => {
+                let ratio = if ratio.is_nan() { 1.0 } else { ratio.clamp(0.0, 1.0) };
+                // the small epsilon stops e.g. (1 - 0.9) * 10 from
+                // rounding down to 0 because of float imprecision
+                let d = ((1.0 - ratio) * len as f64 + 1e-9).floor();
+                d.min(MAX_RATIO_DISTANCE as f64) as i8
+            }
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::LevenshteinAutomata;
+    use crate::normalized_levenshtein;
+
+    #[test]
+    fn derived_distances() {
+        let auto = Fuzziness::Auto { low: 2, high: 4 };
+        let expected = vec![("", 0), ("a", 0), ("ab", 1), ("abc", 1), ("abcd", 2)];
+        for (src, d) in expected {
+            assert!(auto.max_distance(src) == d);
+        }
+
+        assert!(Fuzziness::Fixed(3).max_distance("") == 3);
+        assert!(Fuzziness::Ratio(1.0).max_distance("abcdefghij") == 0);
+        assert!(Fuzziness::Ratio(0.9).max_distance("abcdefghij") == 1);
+        assert!(Fuzziness::Ratio(0.0).max_distance("abc") == 3);
+        assert!(Fuzziness::Ratio(0.0).max_distance("abcd") == 3);
+        assert!(Fuzziness::Ratio(0.8).max_distance(&"a".repeat(30)) == 3);
+        assert!(Fuzziness::Ratio(f64::NAN).max_distance("abcdefghij") == 0);
+        assert!(Fuzziness::Ratio(0.75).max_distance("日本語文") == 1);
+    }
+
+    #[test]
+    fn ratio_automata() {
+        let src = "levenshtein";
+        let lda = LevenshteinAutomata::with_fuzziness(src, Fuzziness::Ratio(0.8));
+        assert!(lda.details() == (src, 2));
+        // inputs no longer than the source are accepted
+        // exactly when they are similar enough
+        let inputs = vec![
+            "levenshtein", "lavenshtein", "lavenshtien",
+            "levensht", "xxvenshtexn", "levenstein"
+        ];
+        for input in inputs {
+            println!("Checking '{}'", input);
+            assert!(lda.check(input) == (normalized_levenshtein(src, input) >= 0.8));
+        }
+        // longer inputs are held to the source length
+        let lda = LevenshteinAutomata::with_fuzziness("abcdefghi", Fuzziness::Ratio(0.9));
+        assert!(normalized_levenshtein("abcdefghi", "abcdefghiX") >= 0.9);
+        assert!(!lda.check("abcdefghiX"));
+
+        // out of range ratios still build
+        assert!(LevenshteinAutomata::with_fuzziness("abc", Fuzziness::Ratio(f64::NAN)).details() == ("abc", 0));
+        let long = "a".repeat(200);
+        let lda = LevenshteinAutomata::with_fuzziness(&long, Fuzziness::Ratio(0.0));
+        assert!(lda.max_distance() == 3 && lda.check(&long[3..]) && !lda.check(&long[4..]));
+    }
+}
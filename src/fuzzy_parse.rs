@@ -0,0 +1,149 @@
+use std::error::Error;
+use std::fmt;
+use crate::{LevenshteinAutomata, levenshtein_distance};
+
+/// The error returned when a string doesn't name any known variant
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ParseFuzzyError {
+    input: String,
+    suggestion: Option<String>
+}
+
+impl ParseFuzzyError {
+    /// Returns the string that failed to parse
+    pub fn input(&self) -> &str {
+        &self.input
+    }
+
+    /// Returns the closest known name, if any was close enough to suggest
+    pub fn suggestion(&self) -> Option<&str> {
+        self.suggestion.as_deref()
+    }
+}
+
+impl fmt::Display for ParseFuzzyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.suggestion {
+            Some(s) => write!(f, "unknown variant '{}', did you mean '{}'?", self.input, s),
+            None => write!(f, "unknown variant '{}'", self.input)
+        }
+    }
+}
+
+impl Error for ParseFuzzyError {}
+
+/// Controls how `resolve` treats inputs that aren't an exact match
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ResolveOptions {
+    /// names up to this distance from the input are suggested
+    pub max_distance: i8,
+    /// names up to this distance from the input are accepted outright,
+    /// provided no other name is as close
+    pub accept_distance: i8,
+    /// if `true`, names and input are compared in lowercase
+    pub case_insensitive: bool
+}
+
+impl Default for ResolveOptions {
+    fn default() -> Self {
+        ResolveOptions {
+            max_distance: 2,
+            accept_distance: 0,
+            case_insensitive: false
+        }
+    }
+}
+
+/// Resolves a string to one of a set of names, tolerating typos
+///
+/// This is the runtime half of `#[derive(FuzzyFromStr)]`, but can be
+/// used on its own.
+///
+/// # Arguments
+/// * `input` - the string to resolve
+/// * `names` - `(name, value)` pairs; several names may share a value,
+///   and the first is suggested when names are equally close
+/// * `options` - how far inputs may be from a name
+///
+/// # Returns
+/// * the value of the matching name, or an error carrying
+///   the closest name as a suggestion
+///
+/// # Examples
+/// ```
+/// use levenshtein_lite::fuzzy_parse::{resolve, ResolveOptions};
+/// let names = [("warning", 0), ("error", 1)];
+/// let options = ResolveOptions::default();
+/// assert!(resolve("error", &names, options) == Ok(1));
+/// let err = resolve("warnnig", &names, options).unwrap_err();
+/// assert!(err.to_string() == "unknown variant 'warnnig', did you mean 'warning'?");
+/// ```
+pub fn resolve<T: Copy + PartialEq>(input: &str, names: &[(&str, T)], options: ResolveOptions) -> Result<T, ParseFuzzyError> {
+    let fold = |s: &str| if options.case_insensitive { s.to_lowercase() } else { s.to_string() };
+    let folded = fold(input);
+    if let Some(&(_, value)) = names.iter().find(|(name, _)| fold(name) == folded) {
+        return Ok(value);
+    }
+
+    let lda = LevenshteinAutomata::new(&folded, options.max_distance);
+    let mut best: Option<(i32, &str, T)> = None;
+    let mut tied = false;
+    for &(name, value) in names {
+        let folded_name = fold(name);
+        if !lda.check(&folded_name) {
+            continue;
+        }
+        let d = levenshtein_distance(&folded, &folded_name);
+        match best {
+            Some((best_d, _, _)) if d > best_d => (),
+            Some((best_d, _, best_value)) if d == best_d => {
+                // aliases of the same value don't make it ambiguous
+                tied |= value != best_value;
+            },
+            _ => {
+                best = Some((d, name, value));
+                tied = false;
+            }
+        }
+    }
+    match best {
+        Some((d, _, value)) if !tied && d <= options.accept_distance as i32 => Ok(value),
+        _ => Err(ParseFuzzyError {
+            input: input.to_string(),
+            suggestion: best.map(|(_, name, _)| name.to_string())
+        })
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn basic_resolve() {
+        let names = [("Warning", 'w'), ("warn", 'w'), ("Error", 'e'), ("Info", 'i')];
+        let strict = ResolveOptions::default();
+        assert!(resolve("Warning", &names, strict) == Ok('w'));
+        assert!(resolve("warn", &names, strict) == Ok('w'));
+
+        let err = resolve("Eror", &names, strict).unwrap_err();
+        assert!(err.input() == "Eror" && err.suggestion() == Some("Error"));
+        let err = resolve("Debug", &names, strict).unwrap_err();
+        assert!(err.suggestion().is_none());
+        assert!(err.to_string() == "unknown variant 'Debug'");
+
+        let lenient = ResolveOptions {
+            accept_distance: 1,
+            case_insensitive: true,
+            ..ResolveOptions::default()
+        };
+        assert!(resolve("INFO", &names, lenient) == Ok('i'));
+        assert!(resolve("eror", &names, lenient) == Ok('e'));
+        // two edits away is suggested, not accepted
+        assert!(resolve("wrnin", &names, lenient).unwrap_err().suggestion() == Some("Warning"));
+        // equally close names are never accepted
+        let names = [("cat", 0), ("car", 1)];
+        assert!(resolve("caX", &names, lenient).unwrap_err().suggestion() == Some("cat"));
+    }
+}
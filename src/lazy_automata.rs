@@ -0,0 +1,313 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+use crate::levenshtein_automata::{expand, normalize, TChar, SiDaccPairs};
+
+struct LazyEdge<T> {
+    tchar: TChar<T>,
+    pairs: SiDaccPairs,
+    // the cached state the edge leads to, once it is known
+    target: Option<usize>
+}
+
+struct LazyNode<T> {
+    pairs: SiDaccPairs,
+    accepting: bool,
+    // computed the first time a character is read in this state
+    edges: Option<Vec<LazyEdge<T>>>
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum StateRef {
+    Cached(usize),
+    // a state that didn't fit under the state limit
+    Uncached(SiDaccPairs)
+}
+
+/// A state of a `LazyLevenshteinAutomata`, see `LazyLevenshteinAutomata::step`
+///
+/// States are only meaningful to the automata that produced them.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct LazyState(StateRef);
+
+/// A Levenshtein Automata that is determinized on demand
+///
+/// Where `LevenshteinAutomata` builds every state upfront, this automata
+/// only builds the states that inputs actually reach, and caches them for
+/// later inputs. It accepts exactly the same inputs, but is far cheaper to
+/// instantiate for long sources and large distances.
+pub struct LazyLevenshteinAutomata<T = char> {
+    src: Vec<T>,
+    max_distance: i8,
+    state_limit: Option<usize>,
+    states: Vec<LazyNode<T>>,
+    lookup: HashMap<SiDaccPairs, usize>
+}
+
+impl LazyLevenshteinAutomata {
+    /// Instantiates a new lazy automata
+    ///
+    /// # Arguments
+    /// * `src` - the string that inputs will be compared with
+    /// * `max_distance` - the maximum acceptable Levenshtein Distance
+    ///   that the automata should account for
+    ///
+    /// # Returns
+    /// * A new `LazyLevenshteinAutomata` instance, holding only its initial state
+    ///
+    /// # Examples
+    /// ```
+    /// use levenshtein_lite::LazyLevenshteinAutomata;
+    /// let mut lda = LazyLevenshteinAutomata::new("abc", 1);
+    /// assert!(lda.check("abx"));
+    /// assert!(!lda.check("axx"));
+    /// ```
+    pub fn new(src: &str, max_distance: i8) -> Self {
+        Self::from_symbols(src.chars(), max_distance)
+    }
+
+    /// Checks an input string against the source string,
+    /// building any states it needs along the way
+    ///
+    /// # Arguments
+    /// * `input` - the string to check against the source
+    ///
+    /// # Returns
+    /// * `true` if `LevenshteinDistance(src, input) <= max_distance`
+    pub fn check(&mut self, input: &str) -> bool {
+        self.check_symbols(input.chars())
+    }
+}
+
+impl<T: Eq + Hash + Ord + Clone> LazyLevenshteinAutomata<T> {
+    /// Instantiates a new lazy automata over an arbitrary symbol type
+    ///
+    /// # Arguments
+    /// * `src` - the sequence that inputs will be compared with
+    /// * `max_distance` - the maximum acceptable Levenshtein Distance
+    ///   that the automata should account for
+    ///
+    /// # Returns
+    /// * A new `LazyLevenshteinAutomata` instance, holding only its initial state
+    pub fn from_symbols<I: IntoIterator<Item = T>>(src: I, max_distance: i8) -> Self {
+        let mut lda = LazyLevenshteinAutomata {
+            src: src.into_iter().collect(),
+            max_distance,
+            state_limit: None,
+            states: vec![],
+            lookup: HashMap::new()
+        };
+        lda.intern(vec![(0, 0)]);
+        lda
+    }
+
+    /// Limits the number of states the automata caches
+    ///
+    /// Once the limit is reached, new states are still computed
+    /// as inputs reach them, but are dropped again afterwards.
+    /// The initial state is always cached.
+    ///
+    /// # Arguments
+    /// * `limit` - the maximum number of cached states
+    ///
+    /// # Examples
+    /// ```
+    /// use levenshtein_lite::LazyLevenshteinAutomata;
+    /// let mut lda = LazyLevenshteinAutomata::new("Levenshtein", 3).with_state_limit(8);
+    /// assert!(lda.check("Levensthein"));
+    /// assert!(lda.num_cached_states() <= 8);
+    /// ```
+    pub fn with_state_limit(mut self, limit: usize) -> Self {
+        self.state_limit = Some(limit);
+        self
+    }
+
+    /// Checks an input sequence against the source sequence,
+    /// building any states it needs along the way
+    ///
+    /// # Arguments
+    /// * `input` - the symbols to check against the source
+    ///
+    /// # Returns
+    /// * `true` if `LevenshteinDistance(src, input) <= max_distance`
+    pub fn check_symbols<I: IntoIterator<Item = T>>(&mut self, input: I) -> bool {
+        let mut state = self.start();
+        for c in input {
+            match self.step(&state, &c) {
+                Some(next) => state = next,
+                None => return false
+            }
+        }
+        self.is_accepting(&state)
+    }
+
+    /// Returns the initial state of the automata
+    pub fn start(&self) -> LazyState {
+        LazyState(StateRef::Cached(0))
+    }
+
+    /// Reads one symbol of input
+    ///
+    /// # Arguments
+    /// * `state` - the state before reading `symbol`
+    /// * `symbol` - the next symbol of input
+    ///
+    /// # Returns
+    /// * the state after reading `symbol`, or `None` if no
+    ///   input continuing this way can be accepted
+    ///
+    /// # Examples
+    /// ```
+    /// use levenshtein_lite::LazyLevenshteinAutomata;
+    /// let mut lda = LazyLevenshteinAutomata::new("abc", 0);
+    /// let state = lda.start();
+    /// let state = lda.step(&state, &'a').unwrap();
+    /// assert!(!lda.is_accepting(&state));
+    /// assert!(lda.step(&state, &'x').is_none());
+    /// ```
+    pub fn step(&mut self, state: &LazyState, symbol: &T) -> Option<LazyState> {
+        match state.0 {
+            StateRef::Cached(idx) => {
+                if self.states[idx].edges.is_none() {
+                    let edges = self.edges(&self.states[idx].pairs);
+                    self.states[idx].edges = Some(edges);
+                }
+                let edges = self.states[idx].edges.as_ref().unwrap();
+                let edge_idx = find_edge(edges, symbol)?;
+                if let Some(target) = edges[edge_idx].target {
+                    return Some(LazyState(StateRef::Cached(target)));
+                }
+                let pairs = edges[edge_idx].pairs.clone();
+                let next = self.intern(pairs);
+                if let StateRef::Cached(target) = next {
+                    self.states[idx].edges.as_mut().unwrap()[edge_idx].target = Some(target);
+                }
+                Some(LazyState(next))
+            },
+            StateRef::Uncached(ref pairs) => {
+                let mut edges = self.edges(pairs);
+                let edge_idx = find_edge(&edges, symbol)?;
+                let pairs = edges.swap_remove(edge_idx).pairs;
+                Some(LazyState(self.intern(pairs)))
+            }
+        }
+    }
+
+    /// Returns `true` if the automata accepts inputs ending in `state`
+    pub fn is_accepting(&self, state: &LazyState) -> bool {
+        match state.0 {
+            StateRef::Cached(idx) => self.states[idx].accepting,
+            StateRef::Uncached(ref pairs) => self.accepting(pairs)
+        }
+    }
+
+    /// Returns the number of states built and cached so far
+    pub fn num_cached_states(&self) -> usize {
+        self.states.len()
+    }
+
+    fn edges(&self, pairs: &SiDaccPairs) -> Vec<LazyEdge<T>> {
+        let (_, transitions) = expand(&self.src, pairs, self.max_distance);
+        transitions
+            .into_iter()
+            .map(|(tchar, pairs)| LazyEdge {
+                tchar,
+                pairs: normalize(pairs),
+                target: None
+            })
+            .collect()
+    }
+
+    fn accepting(&self, pairs: &SiDaccPairs) -> bool {
+        // decided as for `LevenshteinAutomata`, so both accept the same inputs
+        let (accepting, _) = expand(&self.src, pairs, self.max_distance);
+        accepting
+    }
+
+    fn intern(&mut self, pairs: SiDaccPairs) -> StateRef {
+        if let Some(&idx) = self.lookup.get(&pairs) {
+            return StateRef::Cached(idx);
+        }
+        let full = self.state_limit.is_some_and(|limit| self.states.len() >= limit);
+        if full && !self.states.is_empty() {
+            return StateRef::Uncached(pairs);
+        }
+        let idx = self.states.len();
+        self.states.push(LazyNode {
+            accepting: self.accepting(&pairs),
+            pairs: pairs.clone(),
+            edges: None
+        });
+        self.lookup.insert(pairs, idx);
+        StateRef::Cached(idx)
+    }
+}
+
+// Finds the edge a symbol follows: its own if there is one, otherwise ANY
+fn find_edge<T: Eq>(edges: &[LazyEdge<T>], symbol: &T) -> Option<usize> {
+    let mut any_idx = None;
+    for (idx, edge) in edges.iter().enumerate() {
+        match edge.tchar {
+            TChar::Char(ref c) if c == symbol => return Some(idx),
+            TChar::Any => any_idx = Some(idx),
+            _ => ()
+        }
+    }
+    any_idx
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{LevenshteinAutomata, levenshtein_distance};
+    use rand::{Rng, SeedableRng, rngs::StdRng};
+
+    #[test]
+    fn lazy_agrees_with_eager() {
+        let mut rng = StdRng::seed_from_u64(41);
+        let alphabet = ['a', 'b', 'c', 'd'];
+        let random_string = |rng: &mut StdRng, max_len: usize| {
+            let len = rng.gen_range(0..=max_len);
+            (0..len).map(|_| alphabet[rng.gen_range(0..alphabet.len())]).collect::<String>()
+        };
+        for _ in 0..40 {
+            let src = random_string(&mut rng, 8);
+            for d in 0..=3 {
+                let eager = LevenshteinAutomata::new(&src, d);
+                let mut lazy = LazyLevenshteinAutomata::new(&src, d);
+                let mut capped = LazyLevenshteinAutomata::new(&src, d).with_state_limit(3);
+                for _ in 0..30 {
+                    let input = random_string(&mut rng, 11);
+                    let expected = levenshtein_distance(&src, &input) <= d as i32;
+                    assert!(eager.check(&input) == expected);
+                    assert!(lazy.check(&input) == expected, "{} {} {}", src, input, d);
+                    assert!(capped.check(&input) == expected);
+                }
+                assert!(capped.num_cached_states() <= 3);
+            }
+        }
+
+        // even out of range distances agree
+        for d in [-1, -5] {
+            let eager = LevenshteinAutomata::new("abc", d);
+            let mut lazy = LazyLevenshteinAutomata::new("abc", d);
+            for input in ["", "ab", "abc", "abx", "abcd"] {
+                assert!(lazy.check(input) == eager.check(input), "{} {}", input, d);
+            }
+            assert!(!lazy.check("ab"));
+        }
+    }
+
+    #[test]
+    fn builds_on_demand() {
+        let src = "the quick brown fox jumps over the lazy dog";
+        let mut lda = LazyLevenshteinAutomata::new(src, 4);
+        assert!(lda.num_cached_states() == 1);
+        assert!(lda.check("the quick brwn fox jumps ovr the lazy dgo"));
+        assert!(!lda.check("the slow brown cat sleeps under the lazy dog"));
+        let after_two = lda.num_cached_states();
+        // repeating an input reuses the cached states
+        assert!(lda.check("the quick brwn fox jumps ovr the lazy dgo"));
+        assert!(lda.num_cached_states() == after_two);
+    }
+}
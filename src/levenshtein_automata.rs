@@ -1,72 +1,136 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
+use std::convert::Infallible;
+use std::error::Error;
+use std::fmt::{self, Display};
+use std::hash::{Hash, Hasher};
+use std::io::{self, Read};
 use std::rc::Rc;
 use std::cell::RefCell;
 use std::cmp::min;
+use std::mem::size_of;
+use std::ops::RangeInclusive;
+use std::sync::OnceLock;
+use crate::fuzziness::Fuzziness;
+use crate::verify::{self, Counterexample};
 
-#[derive(Eq, Hash, PartialEq, Clone, Copy, PartialOrd, Ord)]
-enum TChar {
-    Char(char),
+#[derive(Eq, Hash, PartialEq, Clone, PartialOrd, Ord, Debug)]
+pub(crate) enum TChar<T> {
+    Char(T),
     Any
 }
+
+/// The label of an edge in a `LevenshteinAutomata`
+#[derive(Debug, Eq, Hash, PartialEq, Clone, Copy, PartialOrd, Ord)]
+pub enum Label<T = char> {
+    /// the edge is taken on this specific symbol
+    Char(T),
+    /// the edge is taken on any symbol without an edge of its own
+    Any
+}
+
+impl<T> From<TChar<T>> for Label<T> {
+    fn from(tchar: TChar<T>) -> Self {
+        match tchar {
+            TChar::Char(c) => Label::Char(c),
+            TChar::Any => Label::Any
+        }
+    }
+}
+
+/// Which part of an input must match the source of a `LevenshteinAutomata`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum MatchMode {
+    /// the whole input must match
+    #[default]
+    Full,
+    /// some suffix of the input must match; leading chars are free
+    Suffix,
+    /// some substring of the input must match; leading and trailing chars are free
+    Infix
+}
+
+/// Where the source matched within an input, see `LevenshteinAutomata::best_span`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FuzzySpan {
+    /// the byte offset in the input where the match starts
+    pub start: usize,
+    /// the byte offset in the input where the match ends
+    pub end: usize,
+    /// the Levenshtein distance between the source and `input[start..end]`
+    pub distance: i32
+}
+
 type Xi = usize;
-type Si = usize;
-type Dacc = i8;
-type SiDaccPairs = Vec<(Si, Dacc)>;
-type Accepting = bool;
-type LookupKey = (Xi, Accepting, Vec<(TChar, SiDaccPairs)>);
-type NodeLookup = HashMap<LookupKey, Rc<BuildNode>>;
-
-struct BuildNode {
-    transitions: HashMap<TChar, Rc<BuildNode>>,
+pub(crate) type Si = usize;
+pub(crate) type Dacc = i8;
+pub(crate) type SiDaccPairs = Vec<(Si, Dacc)>;
+pub(crate) type Accepting = bool;
+type LookupKey<T> = (Xi, Accepting, Vec<(TChar<T>, SiDaccPairs)>);
+type NodeLookup<T> = HashMap<LookupKey<T>, Rc<BuildNode<T>>>;
+
+struct BuildNode<T> {
+    transitions: HashMap<TChar<T>, Rc<BuildNode<T>>>,
     accepting: bool
 }
 
-struct TempTracker {
-    transitions: HashMap<TChar, SiDaccPairs>
+struct TempTracker<T> {
+    transitions: HashMap<TChar<T>, SiDaccPairs>
 }
 
-impl TempTracker {
+impl<T: Eq + Hash + Ord + Clone> TempTracker<T> {
     pub fn new() -> Self {
         TempTracker {
             transitions: HashMap::new()
         }
     }
 
-    pub fn add(&mut self, key: TChar, pair: (Si, Dacc)) {
+    pub fn add(&mut self, key: TChar<T>, pair: (Si, Dacc)) {
         if let Some(v) = self.transitions.get_mut(&key) {
             v.push(pair);
         } else {
-            let mut v = vec![];
-            v.push(pair);
-            self.transitions.insert(key, v);
+            self.transitions.insert(key, vec![pair]);
+        }
+    }
+
+    pub fn merge_any(&mut self) {
+        // `check` only falls back to ANY when a character has no
+        // transition of its own, so every specific transition must
+        // also lead to wherever ANY would
+        if let Some(any_pairs) = self.transitions.get(&TChar::Any).cloned() {
+            for (tchar, pairs) in self.transitions.iter_mut() {
+                if *tchar != TChar::Any {
+                    pairs.extend_from_slice(&any_pairs);
+                }
+            }
         }
     }
 
-    pub fn key(&self) -> Vec<(TChar, SiDaccPairs)> {
+    pub fn key(&self) -> Vec<(TChar<T>, SiDaccPairs)> {
         let mut key = self.transitions
             .iter()
-            .map(|(&tchar, pairs)| {
+            .map(|(tchar, pairs)| {
                 let mut sorted_pairs = pairs.clone();
                 sorted_pairs.sort();
-                (tchar, sorted_pairs)
+                sorted_pairs.dedup();
+                (tchar.clone(), sorted_pairs)
             })
-            .collect::<Vec<(TChar, SiDaccPairs)>>();
+            .collect::<Vec<(TChar<T>, SiDaccPairs)>>();
         key.sort();
         key
     }
 }
 
-fn build<'a>(
-    src: &Vec<char>,
-    x_i: Xi,
-    si_dacc: SiDaccPairs,
-    max_distance: i8,
-    lookup: &'a RefCell<NodeLookup>
-) -> Rc<BuildNode> {
+// Computes whether a set of (src position, distance) pairs is accepting,
+// and the pairs reached from it on each character
+pub(crate) fn expand<T: Eq + Hash + Ord + Clone>(
+    src: &[T],
+    si_dacc: &[(Si, Dacc)],
+    max_distance: i8
+) -> (Accepting, Vec<(TChar<T>, SiDaccPairs)>) {
     let mut transitions = TempTracker::new();
 
     let mut accepting = false;
-    for (si, dacc) in si_dacc {
+    for &(si, dacc) in si_dacc {
         if si >= src.len() {
             if dacc < max_distance {
                 // in case of a mismatch with edit budget remaining,
@@ -80,9 +144,9 @@ fn build<'a>(
             accepting = true;
             continue;
         }
-        let match_char = TChar::Char(src[si]);
+        let match_char = TChar::Char(src[si].clone());
         // match: advance the src comparison position at zero cost
-        transitions.add(match_char, (si + 1, dacc));
+        transitions.add(match_char.clone(), (si + 1, dacc));
 
         // we need to "look ahead" to match when deletions occur
         for offset_i8 in 1..(max_distance - dacc + 1) {
@@ -96,17 +160,17 @@ fn build<'a>(
             }
             // in case of a deletion, try to match against the character
             // "offset" positions forward
-            let cmp_char = TChar::Char(src[si + offset]);
+            let cmp_char = TChar::Char(src[si + offset].clone());
             if cmp_char != match_char {
                 // if match during lookahead, advance the src comparison position
                 // by 1, at the cost of "offset" edits
-                transitions.add(cmp_char, (si + offset + 1, dacc + offset_i8));
+                transitions.add(cmp_char.clone(), (si + offset + 1, dacc + offset_i8));
                 // this specific character could also represent an insertion
                 // or a substitution, so account for that too
                 // by:
                 //  insert: retain src comparison position at cost of 1 edit
                 //  sub: advancing src comparison position by 1, at the cost of 1 edit
-                transitions.add(cmp_char, (si, dacc + 1));
+                transitions.add(cmp_char.clone(), (si, dacc + 1));
                 transitions.add(cmp_char, (si + 1, dacc + 1));
             }
         }
@@ -126,14 +190,34 @@ fn build<'a>(
         }
     }
 
-    let key: LookupKey = (x_i, accepting, transitions.key());
+    transitions.merge_any();
+    (accepting, transitions.key())
+}
+
+fn build<T: Eq + Hash + Ord + Clone>(
+    src: &[T],
+    x_i: Xi,
+    si_dacc: SiDaccPairs,
+    min_distance: i8,
+    max_distance: i8,
+    lookup: &RefCell<NodeLookup<T>>
+) -> Rc<BuildNode<T>> {
+    let (within_max, transitions) = expand(src, &si_dacc, max_distance);
+    // if the input ended here, the rest of the src would be deleted;
+    // the cheapest way to do so is the distance of the input so far
+    let distance = si_dacc
+        .iter()
+        .map(|&(si, dacc)| dacc as usize + (src.len() - si))
+        .min();
+    let accepting = within_max && distance.is_some_and(|d| d >= min_distance.max(0) as usize);
+    let key: LookupKey<T> = (x_i, accepting, transitions);
     if lookup.borrow().contains_key(&key) {
         lookup.borrow().get(&key).unwrap().clone()
     } else {
         let mut next_transitions = HashMap::new();
-        for (tchar, si_dacc) in transitions.key() {
+        for (tchar, si_dacc) in key.2.clone() {
             let next_node = {
-                build(src, x_i + 1, si_dacc, max_distance, lookup)
+                build(src, x_i + 1, si_dacc, min_distance, max_distance, lookup)
             };
             next_transitions.insert(tchar, next_node);
         }
@@ -145,6 +229,15 @@ fn build<'a>(
     }
 }
 
+// A position reached with a smaller distance makes the same position
+// at a larger distance redundant, so only the former is kept
+pub(crate) fn normalize(mut pairs: SiDaccPairs) -> SiDaccPairs {
+    pairs.sort();
+    pairs.dedup_by_key(|&mut (si, _)| si);
+    pairs
+}
+
+#[derive(PartialEq, Eq, Hash, Clone, Debug)]
 struct Head {
     has_children: bool,
     trns_start: usize,
@@ -152,74 +245,307 @@ struct Head {
     accepting: bool
 }
 
-struct Transition {
-    tchar: TChar,
+#[derive(PartialEq, Eq, Hash, Clone, Debug)]
+struct Transition<T> {
+    tchar: TChar<T>,
     points_to: usize
 }
 
-fn flatten(
-    node: Rc<BuildNode>,
-    head_array: &RefCell<Vec<Head>>,
-    trns_array: &RefCell<Vec<Transition>>,
-    lookup: &RefCell<HashMap<usize, usize>>
-) -> usize {
-    let node_ptr = Rc::as_ptr(&node) as usize;
-    {
-        if lookup.borrow().contains_key(&node_ptr) {
-            // this node has already been placed
-            return *lookup.borrow().get(&node_ptr).unwrap();
+fn flatten<T: Eq + Hash + Ord + Clone>(root: Rc<BuildNode<T>>) -> (Vec<Head>, Vec<Transition<T>>) {
+    let mut heads = vec![];
+    let mut transitions = vec![];
+    // nodes are numbered in breadth first order, visiting edges
+    // in sorted order, so that equal automata are laid out equally
+    let mut placed = HashMap::new();
+    let mut queue = VecDeque::new();
+    placed.insert(Rc::as_ptr(&root) as usize, 0);
+    queue.push_back(root);
+    while let Some(node) = queue.pop_front() {
+        let mut edges = node.transitions.iter().collect::<Vec<(&TChar<T>, &Rc<BuildNode<T>>)>>();
+        edges.sort_by(|a, b| a.0.cmp(b.0));
+        let trns_start = transitions.len();
+        for (tchar, next) in edges {
+            let next_ptr = Rc::as_ptr(next) as usize;
+            let points_to = match placed.get(&next_ptr) {
+                Some(&idx) => idx,
+                None => {
+                    let idx = placed.len();
+                    placed.insert(next_ptr, idx);
+                    queue.push_back(next.clone());
+                    idx
+                }
+            };
+            transitions.push(Transition {
+                tchar: tchar.clone(),
+                points_to
+            });
         }
+        let has_children = !node.transitions.is_empty();
+        heads.push(Head {
+            has_children,
+            trns_start,
+            trns_end: if has_children { transitions.len() - 1 } else { trns_start },
+            accepting: node.accepting
+        });
     }
-    let (head_idx, trns_start) = {
-        (head_array.borrow().len(), trns_array.borrow().len())
-    };
-    let has_children = node.transitions.len() > 0;
-    let trns_end = if has_children {
-        trns_start + node.transitions.len() - 1
-    } else {
-        trns_start
-    };
-    let head = Head {
-        has_children,
-        trns_start,
-        trns_end,
-        accepting: node.accepting
+    (heads, transitions)
+}
+
+// Builds the automata of a `MatchMode` with free leading chars. Every state
+// can restart the match at the start of the src, so unlike `build`, states
+// are identified by their (normalized) pairs alone, and may form cycles
+fn build_unanchored<T: Eq + Hash + Ord + Clone>(
+    src: &[T],
+    max_distance: i8,
+    mode: MatchMode
+) -> (Vec<Head>, Vec<Transition<T>>) {
+    let restart = |mut pairs: SiDaccPairs| {
+        pairs.push((0, 0));
+        normalize(pairs)
     };
-    {
-        head_array.borrow_mut().push(head);
-        if !has_children {
-            return head_idx;
-        }
-        let mut trns_mut = trns_array.borrow_mut();
-        for (tchar, _) in node.transitions.iter() {
-            trns_mut.push(Transition {
-                tchar: tchar.clone(),
-                points_to: 0 //tmp
+    let mut heads = vec![];
+    let mut transitions = vec![];
+    // states are numbered in the order they are first reached,
+    // visiting edges in sorted order, as in `flatten`
+    let start = restart(vec![]);
+    let mut placed = HashMap::from([(start.clone(), 0)]);
+    let mut queue = VecDeque::from([start]);
+    while let Some(pairs) = queue.pop_front() {
+        let (accepting, mut edges) = expand(src, &pairs, max_distance);
+        let trns_start = transitions.len();
+        if accepting && mode == MatchMode::Infix {
+            // a substring has matched, so the rest of the input is free
+            transitions.push(Transition {
+                tchar: TChar::Any,
+                points_to: heads.len()
             });
+        } else {
+            if !edges.iter().any(|(tchar, _)| *tchar == TChar::Any) {
+                // a char that matches nothing still restarts the match
+                edges.push((TChar::Any, vec![]));
+            }
+            for (tchar, next) in edges {
+                let next = restart(next);
+                let points_to = match placed.get(&next) {
+                    Some(&idx) => idx,
+                    None => {
+                        let idx = placed.len();
+                        placed.insert(next.clone(), idx);
+                        queue.push_back(next);
+                        idx
+                    }
+                };
+                transitions.push(Transition { tchar, points_to });
+            }
         }
+        heads.push(Head {
+            has_children: true,
+            trns_start,
+            trns_end: transitions.len() - 1,
+            accepting
+        });
     }
-    let (start, end) = {
-        let headref = &head_array.borrow()[head_idx];
-        (headref.trns_start, headref.trns_end)
+    (heads, transitions)
+}
+
+// The distance between `src` and the closest part of `input` that `mode`
+// lets match it, by brute force
+fn closest_part_distance(src: &str, input: &str, mode: MatchMode) -> i32 {
+    let offsets = input
+        .char_indices()
+        .map(|(offset, _)| offset)
+        .chain([input.len()])
+        .collect::<Vec<usize>>();
+    // the parts of the input that may match, by their byte offsets
+    let parts = match mode {
+        MatchMode::Full => vec![(0, input.len())],
+        MatchMode::Suffix => offsets.iter().map(|&start| (start, input.len())).collect(),
+        MatchMode::Infix => {
+            let mut parts = vec![];
+            for (i, &start) in offsets.iter().enumerate() {
+                parts.extend(offsets[i..].iter().map(|&end| (start, end)));
+            }
+            parts
+        }
     };
-    for idx in start..end+1 {
-        let tchar = { trns_array.borrow()[idx].tchar };
-        let points_to = flatten(node.transitions[&tchar].clone(), head_array, trns_array, lookup);
-        let trn = &mut trns_array.borrow_mut()[idx];
-        trn.points_to = points_to;
+    parts.into_iter()
+        .map(|(start, end)| levenshtein_distance(src, &input[start..end]))
+        .min()
+        .unwrap()
+}
+
+/// The error returned when an input to check can't be decoded
+#[derive(Debug)]
+pub enum CheckError {
+    /// an unpaired surrogate at this index of a UTF-16 input
+    InvalidUtf16 { position: usize },
+    /// an invalid or truncated sequence at this byte offset of a UTF-8 input
+    InvalidUtf8 { offset: usize },
+    /// reading the input failed
+    Io(io::Error)
+}
+
+impl Display for CheckError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CheckError::InvalidUtf16 { position } => write!(f, "invalid UTF-16 at position {}", position),
+            CheckError::InvalidUtf8 { offset } => write!(f, "invalid UTF-8 at byte offset {}", offset),
+            CheckError::Io(err) => write!(f, "failed to read input: {}", err)
+        }
+    }
+}
+
+impl Error for CheckError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            CheckError::Io(err) => Some(err),
+            _ => None
+        }
+    }
+}
+
+impl From<io::Error> for CheckError {
+    fn from(err: io::Error) -> Self {
+        CheckError::Io(err)
+    }
+}
+
+// Decodes the chars of a UTF-8 stream, a buffer at a time
+struct Utf8Chars<R> {
+    reader: R,
+    buf: Vec<u8>,
+    start: usize,
+    end: usize,
+    // the offset in the stream of buf[start]
+    offset: usize,
+    eof: bool,
+    failed: bool
+}
+
+impl<R: Read> Utf8Chars<R> {
+    fn new(reader: R) -> Self {
+        Utf8Chars {
+            reader,
+            buf: vec![0; 8192],
+            start: 0,
+            end: 0,
+            offset: 0,
+            eof: false,
+            failed: false
+        }
+    }
+
+    fn fail(&mut self, err: CheckError) -> Option<Result<char, CheckError>> {
+        self.failed = true;
+        Some(Err(err))
+    }
+}
+
+impl<R: Read> Iterator for Utf8Chars<R> {
+    type Item = Result<char, CheckError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.failed {
+            return None;
+        }
+        loop {
+            if self.start < self.end {
+                let width = match self.buf[self.start] {
+                    0x00..=0x7F => 1,
+                    0xC2..=0xDF => 2,
+                    0xE0..=0xEF => 3,
+                    0xF0..=0xF4 => 4,
+                    _ => return self.fail(CheckError::InvalidUtf8 { offset: self.offset })
+                };
+                if self.end - self.start >= width {
+                    let bytes = &self.buf[self.start..self.start + width];
+                    return match std::str::from_utf8(bytes) {
+                        Ok(decoded) => {
+                            self.start += width;
+                            self.offset += width;
+                            decoded.chars().next().map(Ok)
+                        },
+                        Err(_) => self.fail(CheckError::InvalidUtf8 { offset: self.offset })
+                    };
+                }
+            }
+            if self.eof {
+                if self.start < self.end {
+                    // the stream ends partway through a char
+                    return self.fail(CheckError::InvalidUtf8 { offset: self.offset });
+                }
+                return None;
+            }
+            // keep the start of a char split across reads
+            self.buf.copy_within(self.start..self.end, 0);
+            self.end -= self.start;
+            self.start = 0;
+            match self.reader.read(&mut self.buf[self.end..]) {
+                Ok(0) => self.eof = true,
+                Ok(n) => self.end += n,
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => (),
+                Err(err) => return self.fail(CheckError::Io(err))
+            }
+        }
     }
-    // return the head_index so the transition
-    // knows where to point to, after caching the vale
-    lookup.borrow_mut().insert(node_ptr, head_idx);
-    head_idx
 }
 
 /// No-frills implementation of a Levenshtein Automata
-pub struct LevenshteinAutomata {
-    src: String,
+/// 
+/// By default the automata compares strings char by char, but it can
+/// be built over any symbol type, e.g. `&str` to compare phrases word
+/// by word, see `LevenshteinAutomata::from_symbols`.
+/// 
+/// States are laid out canonically, so automata built from the same
+/// source and distance are equal, hash equally and render identically.
+#[derive(Clone)]
+pub struct LevenshteinAutomata<T = char> {
+    src: Vec<T>,
+    // the source as text, collected from `src` the first time `details`
+    // is called; being derived, it is left out of comparisons
+    src_text: OnceLock<String>,
+    min_distance: i8,
     max_distance: i8,
+    mode: MatchMode,
     heads: Vec<Head>,
-    transitions: Vec<Transition>
+    transitions: Vec<Transition<T>>
+}
+
+impl<T: PartialEq> PartialEq for LevenshteinAutomata<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.src == other.src
+            && self.min_distance == other.min_distance
+            && self.max_distance == other.max_distance
+            && self.mode == other.mode
+            && self.heads == other.heads
+            && self.transitions == other.transitions
+    }
+}
+
+impl<T: Eq> Eq for LevenshteinAutomata<T> {}
+
+impl<T: Hash> Hash for LevenshteinAutomata<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.src.hash(state);
+        self.min_distance.hash(state);
+        self.max_distance.hash(state);
+        self.mode.hash(state);
+        self.heads.hash(state);
+        self.transitions.hash(state);
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for LevenshteinAutomata<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LevenshteinAutomata")
+            .field("src", &self.src)
+            .field("min_distance", &self.min_distance)
+            .field("max_distance", &self.max_distance)
+            .field("mode", &self.mode)
+            .field("heads", &self.heads)
+            .field("transitions", &self.transitions)
+            .finish()
+    }
 }
 
 impl LevenshteinAutomata {
@@ -228,7 +554,7 @@ impl LevenshteinAutomata {
     /// # Arguments
     /// * `src` - the string that inputs will be compared with
     /// * `max_distance` - the maximum acceptable Levenshtein Distance
-    ///                    that the automata should account for
+    ///   that the automata should account for
     /// 
     /// # Returns
     /// * A new `LevenshteinAutomata` instance
@@ -241,22 +567,78 @@ impl LevenshteinAutomata {
     /// assert!(!lda.check("axx"));
     /// ```
     pub fn new(src: &str, max_distance: i8) -> Self {
-        let lookup = RefCell::new(HashMap::new());
-        let head = build(&src.chars().collect(),
-                                        0,
-                                        vec![(0, 0)],
-                                        max_distance,
-                                        &lookup);
-        let head_array = RefCell::new(vec![]);
-        let trns_array = RefCell::new(vec![]);
-        let lookup = RefCell::new(HashMap::new());
-        let _ = flatten(head, &head_array, &trns_array, &lookup);
-        LevenshteinAutomata {
-            src: src.to_string(),
-            max_distance,
-            heads: head_array.into_inner(),
-            transitions: trns_array.into_inner()
-        }
+        Self::with_distance_range(src, 0..=max_distance)
+    }
+
+    /// Instantiates a new automata accepting inputs
+    /// within a range of distances from the source
+    /// 
+    /// # Arguments
+    /// * `src` - the string that inputs will be compared with
+    /// * `distances` - the acceptable Levenshtein Distances
+    /// 
+    /// # Returns
+    /// * A new `LevenshteinAutomata` instance, whose `check` returns `true`
+    ///   if `LevenshteinDistance(src, input)` is within `distances`
+    /// 
+    /// # Examples
+    /// ```
+    /// use levenshtein_lite::LevenshteinAutomata;
+    /// let near_duplicates = LevenshteinAutomata::with_distance_range("abc", 1..=2);
+    /// assert!(near_duplicates.check("abx"));
+    /// assert!(!near_duplicates.check("abc"));
+    /// let exactly_one = LevenshteinAutomata::with_distance_range("abc", 1..=1);
+    /// assert!(!exactly_one.check("axx"));
+    /// ```
+    pub fn with_distance_range(src: &str, distances: RangeInclusive<i8>) -> Self {
+        Self::from_symbols_with_distance_range(src.chars(), distances)
+    }
+
+    /// Instantiates a new automata matching part of the input
+    /// rather than all of it
+    /// 
+    /// # Arguments
+    /// * `src` - the string that inputs will be compared with
+    /// * `max_distance` - the maximum acceptable Levenshtein Distance
+    /// * `mode` - which part of the input must match `src`
+    /// 
+    /// # Returns
+    /// * A new `LevenshteinAutomata` instance, whose `check` returns `true` if
+    ///   `LevenshteinDistance(src, part) <= max_distance` for some suffix
+    ///   (`MatchMode::Suffix`) or substring (`MatchMode::Infix`) of the input
+    /// 
+    /// # Examples
+    /// ```
+    /// use levenshtein_lite::{LevenshteinAutomata, MatchMode};
+    /// let contains = LevenshteinAutomata::with_match_mode("error", 1, MatchMode::Infix);
+    /// assert!(contains.check("fatal eror: disk full"));
+    /// let ends_with = LevenshteinAutomata::with_match_mode("error", 1, MatchMode::Suffix);
+    /// assert!(ends_with.check("fatal eror"));
+    /// assert!(!ends_with.check("fatal eror: disk full"));
+    /// ```
+    pub fn with_match_mode(src: &str, max_distance: i8, mode: MatchMode) -> Self {
+        Self::from_symbols_with_match_mode(src.chars(), max_distance, mode)
+    }
+
+    /// Instantiates a new automata whose maximum distance
+    /// is derived from the source string
+    /// 
+    /// # Arguments
+    /// * `src` - the string that inputs will be compared with
+    /// * `fuzziness` - how to derive the maximum acceptable Levenshtein Distance
+    /// 
+    /// # Returns
+    /// * A new `LevenshteinAutomata` instance
+    /// 
+    /// # Examples
+    /// ```
+    /// use levenshtein_lite::{LevenshteinAutomata, Fuzziness};
+    /// let lda = LevenshteinAutomata::with_fuzziness("abcd", Fuzziness::AUTO);
+    /// assert!(lda.check("abxd"));
+    /// assert!(!lda.check("axxd"));
+    /// ```
+    pub fn with_fuzziness(src: &str, fuzziness: Fuzziness) -> Self {
+        Self::new(src, fuzziness.max_distance(src))
     }
 
     /// Checks an input string against the source string
@@ -276,42 +658,177 @@ impl LevenshteinAutomata {
     /// assert!(!lda.check("axx"));
     /// ```
     pub fn check(&self, input: &str) -> bool {
-        let mut head = &self.heads[0];
-        let mut accepting = head.accepting;
-        for c in input.chars() {
-            if !head.has_children {
-                return false;
-            }
-            let mut match_idx: Option<usize> = Option::None;
-            for idx in head.trns_start..head.trns_end+1 {
-                let tref = &self.transitions[idx];
-                match tref.tchar {
-                    TChar::Char(tc) if tc == c => {
-                        match_idx = Some(tref.points_to);
-                        break;
-                    },
-                    TChar::Any => match match_idx {
-                        None => {
-                            match_idx = Some(tref.points_to)
-                        },
-                        _ => ()
-                    },
-                    _ => ()
-                };
-            }
-            match match_idx {
-                Some(i) => {
-                    head = &self.heads[i];
-                    accepting = head.accepting;
-                },
-                None => {
-                    // we have no valid transitions from here,
-                    // hence this isn't a match
-                    return false;
-                }
+        self.check_symbols(input.chars())
+    }
+
+    /// Checks a sequence of chars against the source string
+    /// 
+    /// # Arguments
+    /// * `input` - the chars to check against the source
+    /// 
+    /// # Returns
+    /// * `true` if `LevenshteinDistance(src, input) <= max_distance`
+    /// 
+    /// # Examples
+    /// ```
+    /// use levenshtein_lite::LevenshteinAutomata;
+    /// let lda = LevenshteinAutomata::new("abc", 1);
+    /// let text = ['x', 'a', 'b', 'x', 'y'];
+    /// assert!(lda.check_chars(text[1..4].iter().copied()));
+    /// ```
+    pub fn check_chars<I: IntoIterator<Item = char>>(&self, input: I) -> bool {
+        self.check_symbols(input)
+    }
+
+    /// Checks a UTF-16 encoded input against the source string
+    /// 
+    /// Decoding stops as soon as the input can no longer match,
+    /// so invalid input past that point isn't reported.
+    /// 
+    /// # Arguments
+    /// * `input` - the UTF-16 code units to check against the source
+    /// 
+    /// # Returns
+    /// * `Ok(true)` if `LevenshteinDistance(src, input) <= max_distance`
+    /// * `Err(CheckError::InvalidUtf16)` at the first unpaired surrogate
+    /// 
+    /// # Examples
+    /// ```
+    /// use levenshtein_lite::LevenshteinAutomata;
+    /// let lda = LevenshteinAutomata::new("abc", 1);
+    /// let input = "abx".encode_utf16().collect::<Vec<u16>>();
+    /// assert!(lda.check_utf16(&input).unwrap());
+    /// assert!(lda.check_utf16(&[0x61, 0xD800]).is_err());
+    /// ```
+    pub fn check_utf16(&self, input: &[u16]) -> Result<bool, CheckError> {
+        let mut position = 0;
+        let chars = char::decode_utf16(input.iter().copied()).map(|decoded| match decoded {
+            Ok(c) => {
+                position += c.len_utf16();
+                Ok(c)
+            },
+            Err(_) => Err(CheckError::InvalidUtf16 { position })
+        });
+        self.walk(chars)
+    }
+
+    /// Checks a UTF-8 encoded stream against the source string
+    /// 
+    /// The stream is read and decoded incrementally, and reading
+    /// stops as soon as the input can no longer match.
+    /// 
+    /// # Arguments
+    /// * `reader` - the stream to check against the source
+    /// 
+    /// # Returns
+    /// * `Ok(true)` if `LevenshteinDistance(src, input) <= max_distance`
+    /// * `Err(CheckError::InvalidUtf8)` at the first invalid or truncated sequence
+    /// * `Err(CheckError::Io)` if reading fails
+    /// 
+    /// # Examples
+    /// ```
+    /// use levenshtein_lite::{LevenshteinAutomata, CheckError};
+    /// let lda = LevenshteinAutomata::new("abc", 1);
+    /// assert!(lda.check_reader("abx".as_bytes()).unwrap());
+    /// let err = lda.check_reader(&b"ab\xFF"[..]).unwrap_err();
+    /// assert!(matches!(err, CheckError::InvalidUtf8 { offset: 2 }));
+    /// ```
+    pub fn check_reader<R: Read>(&self, reader: R) -> Result<bool, CheckError> {
+        self.walk(Utf8Chars::new(reader))
+    }
+
+    /// Finds where in an input the source matches best
+    /// 
+    /// Inputs are first checked with the automata, so only
+    /// matching inputs pay for computing the span.
+    /// 
+    /// # Arguments
+    /// * `input` - the string to search
+    /// 
+    /// # Returns
+    /// * the span of `input` closest to the source, preferring the leftmost start,
+    ///   then the longest span; or `None` if the automata rejects `input`.
+    ///   Spans always cover the whole input for `MatchMode::Full`, and always
+    ///   end at the end of the input for `MatchMode::Suffix`
+    /// 
+    /// # Examples
+    /// ```
+    /// use levenshtein_lite::{LevenshteinAutomata, MatchMode};
+    /// let lda = LevenshteinAutomata::with_match_mode("error", 1, MatchMode::Infix);
+    /// let input = "fatal eror: disk full";
+    /// let span = lda.best_span(input).unwrap();
+    /// assert!(&input[span.start..span.end] == "eror" && span.distance == 1);
+    /// ```
+    pub fn best_span(&self, input: &str) -> Option<FuzzySpan> {
+        if !self.check(input) {
+            return None;
+        }
+        let offsets = input
+            .char_indices()
+            .map(|(offset, _)| offset)
+            .chain([input.len()])
+            .collect::<Vec<usize>>();
+        let chars = input.chars().collect::<Vec<char>>();
+        // (distance, start) of the best alignment of src[..i] ending at
+        // each input position, filled in one src char at a time
+        let mut row = (0..=chars.len())
+            .map(|j| match self.mode {
+                MatchMode::Full => (j as i32, 0),
+                _ => (0, j)
+            })
+            .collect::<Vec<(i32, usize)>>();
+        for (i, &src_char) in self.src.iter().enumerate() {
+            let mut next = vec![(i as i32 + 1, row[0].1); chars.len() + 1];
+            for j in 1..=chars.len() {
+                let sub = (row[j - 1].0 + (chars[j - 1] != src_char) as i32, row[j - 1].1);
+                let delete = (row[j].0 + 1, row[j].1);
+                let insert = (next[j - 1].0 + 1, next[j - 1].1);
+                // ties go to the earliest start
+                next[j] = [sub, delete, insert]
+                    .into_iter()
+                    .min()
+                    .unwrap();
             }
+            row = next;
         }
-        accepting
+        let ends = match self.mode {
+            MatchMode::Infix => 0..=chars.len(),
+            _ => chars.len()..=chars.len()
+        };
+        ends.map(|end| (row[end], end))
+            .min_by_key(|&((d, start), end)| (d, start, std::cmp::Reverse(end)))
+            .map(|((distance, start), end)| FuzzySpan {
+                start: offsets[start],
+                end: offsets[end],
+                distance
+            })
+    }
+
+    /// Exhaustively checks the automata against `levenshtein_distance`
+    /// 
+    /// See `verify::verify_against_dp`, which does the same for any matcher.
+    /// For `MatchMode::Suffix` and `MatchMode::Infix`, inputs are compared by
+    /// their closest suffix or substring rather than as a whole.
+    /// 
+    /// # Arguments
+    /// * `alphabet` - the chars inputs are made of
+    /// * `max_len` - the maximum length of the inputs
+    /// 
+    /// # Returns
+    /// * `Ok(())` if every input is checked correctly,
+    ///   otherwise the shortest input that isn't
+    /// 
+    /// # Examples
+    /// ```
+    /// use levenshtein_lite::LevenshteinAutomata;
+    /// let lda = LevenshteinAutomata::new("abc", 1);
+    /// assert!(lda.verify_against_dp(&['a', 'b', 'c', 'x'], 5).is_ok());
+    /// ```
+    pub fn verify_against_dp(&self, alphabet: &[char], max_len: usize) -> Result<(), Counterexample> {
+        let src = self.src.iter().collect::<String>();
+        let distances = self.min_distance..=self.max_distance;
+        let distance_to = |input: &str| closest_part_distance(&src, input, self.mode);
+        verify::verify_with(distances, alphabet, max_len, distance_to, |input| self.check(input))
     }
 
     /// Returns the parameters of the automata
@@ -328,36 +845,434 @@ impl LevenshteinAutomata {
     /// assert!((s, d) == ("abc", 1))
     /// ```
     pub fn details(&self) -> (&str, i8) {
-        (&self.src, self.max_distance)
+        let src = self.src_text.get_or_init(|| self.src.iter().collect());
+        (src, self.max_distance)
     }
 }
 
+impl<T: Eq + Hash + Ord + Clone> LevenshteinAutomata<T> {
+    /// Instantiates a new automata over an arbitrary symbol type
+    /// 
+    /// # Arguments
+    /// * `src` - the sequence that inputs will be compared with
+    /// * `max_distance` - the maximum acceptable Levenshtein Distance
+    ///   that the automata should account for
+    /// 
+    /// # Returns
+    /// * A new `LevenshteinAutomata` instance
+    /// 
+    /// # Examples
+    /// ```
+    /// use levenshtein_lite::LevenshteinAutomata;
+    /// let lda = LevenshteinAutomata::from_symbols(["the", "quick", "fox"], 1);
+    /// assert!(lda.check_symbols(["the", "quick", "brown", "fox"]));
+    /// assert!(!lda.check_symbols(["the", "brown", "dog"]));
+    /// ```
+    pub fn from_symbols<I: IntoIterator<Item = T>>(src: I, max_distance: i8) -> Self {
+        Self::from_symbols_with_distance_range(src, 0..=max_distance)
+    }
 
-/// Computes the Leveshtein distance between two input strings
-/// 
-/// # Arguments
-/// * `a` - a string
-/// * `b` - a string
-/// 
-/// # Returns
-/// * the Levenshtein distance between `a` and `b`
-/// 
-/// # Examples
-/// ```
-/// use levenshtein_lite::levenshtein_distance;
-/// assert!(levenshtein_distance("abc", "abx") == 1);
-/// assert!(levenshtein_distance("abc", "axx") == 2);
-/// ```
-pub fn levenshtein_distance(a: &str, b: &str) -> i32 {
-    let (rowstr, colstr) = (a, b);
-    let mut prev = (0..rowstr.len() as i32 + 1).collect::<Vec<i32>>();
-    let mut current = prev.clone();
-    for (uci, cchar) in colstr.chars().enumerate() {
-        current[0] = uci as i32 + 1;
-        for (uri, rchar) in rowstr.chars().enumerate() {
-            let ri = uri + 1;
-            let r_insert_d = prev[ri] + 1;
-            let r_del_d = current[ri - 1] + 1;
+    /// Instantiates a new automata over an arbitrary symbol type,
+    /// accepting inputs within a range of distances from the source
+    /// 
+    /// # Arguments
+    /// * `src` - the sequence that inputs will be compared with
+    /// * `distances` - the acceptable Levenshtein Distances
+    /// 
+    /// # Returns
+    /// * A new `LevenshteinAutomata` instance
+    pub fn from_symbols_with_distance_range<I: IntoIterator<Item = T>>(
+        src: I,
+        distances: RangeInclusive<i8>
+    ) -> Self {
+        let (min_distance, max_distance) = distances.into_inner();
+        let src = src.into_iter().collect::<Vec<T>>();
+        let lookup = RefCell::new(HashMap::new());
+        let head = build(&src,
+                                        0,
+                                        vec![(0, 0)],
+                                        min_distance,
+                                        max_distance,
+                                        &lookup);
+        let (heads, transitions) = flatten(head);
+        LevenshteinAutomata {
+            src,
+            src_text: OnceLock::new(),
+            min_distance,
+            max_distance,
+            mode: MatchMode::Full,
+            heads,
+            transitions
+        }
+    }
+
+    /// Instantiates a new automata over an arbitrary symbol type,
+    /// matching part of the input rather than all of it
+    /// 
+    /// # Arguments
+    /// * `src` - the sequence that inputs will be compared with
+    /// * `max_distance` - the maximum acceptable Levenshtein Distance
+    /// * `mode` - which part of the input must match `src`
+    /// 
+    /// # Returns
+    /// * A new `LevenshteinAutomata` instance
+    pub fn from_symbols_with_match_mode<I: IntoIterator<Item = T>>(
+        src: I,
+        max_distance: i8,
+        mode: MatchMode
+    ) -> Self {
+        if mode == MatchMode::Full {
+            return Self::from_symbols(src, max_distance);
+        }
+        let src = src.into_iter().collect::<Vec<T>>();
+        let (heads, transitions) = build_unanchored(&src, max_distance, mode);
+        LevenshteinAutomata {
+            src,
+            src_text: OnceLock::new(),
+            min_distance: 0,
+            max_distance,
+            mode,
+            heads,
+            transitions
+        }
+    }
+
+    /// Checks an input sequence against the source sequence
+    /// underlying the automata
+    /// 
+    /// # Arguments
+    /// * `input` - the symbols to check against the source
+    /// 
+    /// # Returns
+    /// * `true` if `LevenshteinDistance(src, input) <= max_distance`
+    pub fn check_symbols<I: IntoIterator<Item = T>>(&self, input: I) -> bool {
+        match self.walk(input.into_iter().map(Ok::<T, Infallible>)) {
+            Ok(accepted) => accepted,
+            Err(never) => match never {}
+        }
+    }
+
+    // Runs the automata over symbols that may fail to decode
+    fn walk<I, E>(&self, input: I) -> Result<bool, E>
+    where
+        I: IntoIterator<Item = Result<T, E>>
+    {
+        let mut state = 0;
+        for c in input {
+            match self.step(state, &c?) {
+                Some(next) => state = next,
+                None => {
+                    // we have no valid transitions from here,
+                    // hence this isn't a match
+                    return Ok(false);
+                }
+            }
+        }
+        Ok(self.heads[state].accepting)
+    }
+
+    // Follows the edge of `state` taken on `symbol`, if there is one
+    fn step(&self, state: usize, symbol: &T) -> Option<usize> {
+        let head = &self.heads[state];
+        if !head.has_children {
+            return None;
+        }
+        let mut any_target = None;
+        for trn in &self.transitions[head.trns_start..head.trns_end + 1] {
+            match trn.tchar {
+                TChar::Char(ref tc) if tc == symbol => return Some(trn.points_to),
+                TChar::Any => any_target = Some(trn.points_to),
+                _ => ()
+            }
+        }
+        any_target
+    }
+
+    /// Returns the source sequence of the automata
+    pub fn symbols(&self) -> &[T] {
+        &self.src
+    }
+
+    /// Returns the maximum distance of the automata
+    pub fn max_distance(&self) -> i8 {
+        self.max_distance
+    }
+
+    /// Returns which part of the input must match the source
+    pub fn match_mode(&self) -> MatchMode {
+        self.mode
+    }
+
+    /// Returns the minimum distance of the automata,
+    /// which is `0` unless it was built with a distance range
+    pub fn min_distance(&self) -> i8 {
+        self.min_distance
+    }
+
+    /// Returns the number of states in the automata
+    /// 
+    /// States are identified by indices in `0..num_states()`,
+    /// and the initial state is always `0`.
+    pub fn num_states(&self) -> usize {
+        self.heads.len()
+    }
+
+    /// Returns the number of edges in the automata
+    pub fn num_transitions(&self) -> usize {
+        self.transitions.len()
+    }
+
+    /// Returns an iterator over the states of the automata
+    pub fn states(&self) -> impl Iterator<Item = usize> {
+        0..self.heads.len()
+    }
+
+    /// Returns `true` if the automata accepts inputs ending in `state`
+    /// 
+    /// # Panics
+    /// * if `state` is not a state of the automata
+    pub fn is_accepting(&self, state: usize) -> bool {
+        self.heads[state].accepting
+    }
+
+    /// Returns an iterator over the accepting states of the automata
+    pub fn accepting_states(&self) -> impl Iterator<Item = usize> + '_ {
+        self.states().filter(|&state| self.is_accepting(state))
+    }
+
+    /// Returns the outgoing edges of a state
+    /// 
+    /// A symbol follows the edge labelled with it if there is one,
+    /// and otherwise the `Label::Any` edge, if there is one.
+    /// 
+    /// # Arguments
+    /// * `state` - a state of the automata
+    /// 
+    /// # Returns
+    /// * an iterator of `(label, target state)` pairs
+    /// 
+    /// # Panics
+    /// * if `state` is not a state of the automata
+    /// 
+    /// # Examples
+    /// ```
+    /// use levenshtein_lite::{LevenshteinAutomata, Label};
+    /// let lda = LevenshteinAutomata::new("a", 0);
+    /// let edges = lda.edges(0).collect::<Vec<(Label, usize)>>();
+    /// assert!(edges.len() == 1 && edges[0].0 == Label::Char('a'));
+    /// assert!(lda.is_accepting(edges[0].1));
+    /// ```
+    pub fn edges(&self, state: usize) -> impl Iterator<Item = (Label<T>, usize)> + '_ {
+        let head = &self.heads[state];
+        let trns = if head.has_children {
+            &self.transitions[head.trns_start..head.trns_end + 1]
+        } else {
+            &self.transitions[0..0]
+        };
+        trns.iter().map(|trn| (Label::from(trn.tchar.clone()), trn.points_to))
+    }
+
+    /// Lists the sequences over an alphabet that the automata accepts
+    /// 
+    /// `Label::Any` edges stand for every symbol of the alphabet
+    /// that has no edge of its own.
+    /// 
+    /// # Arguments
+    /// * `alphabet` - the symbols sequences are made of
+    /// * `max_len` - the maximum length of the sequences
+    /// 
+    /// # Returns
+    /// * the accepted sequences of length `<= max_len`, in lexicographic order
+    /// 
+    /// # Examples
+    /// ```
+    /// use levenshtein_lite::LevenshteinAutomata;
+    /// let lda = LevenshteinAutomata::new("ab", 1);
+    /// let accepted = lda.accepted_strings(&['a', 'b'], 2)
+    ///     .into_iter()
+    ///     .map(|s| s.into_iter().collect::<String>())
+    ///     .collect::<Vec<String>>();
+    /// assert!(accepted == vec!["a", "aa", "ab", "b", "bb"]);
+    /// ```
+    pub fn accepted_strings(&self, alphabet: &[T], max_len: usize) -> Vec<Vec<T>> {
+        let mut alphabet = alphabet.to_vec();
+        alphabet.sort();
+        alphabet.dedup();
+        let mut accepted = vec![];
+        let mut prefix = vec![];
+        self.collect_accepted(0, &alphabet, max_len, &mut prefix, &mut accepted);
+        accepted
+    }
+
+    fn collect_accepted(
+        &self,
+        state: usize,
+        alphabet: &[T],
+        remaining: usize,
+        prefix: &mut Vec<T>,
+        accepted: &mut Vec<Vec<T>>
+    ) {
+        // a prefix sorts before its extensions, so emit it first
+        if self.heads[state].accepting {
+            accepted.push(prefix.clone());
+        }
+        if remaining == 0 {
+            return;
+        }
+        for symbol in alphabet {
+            if let Some(next) = self.step(state, symbol) {
+                prefix.push(symbol.clone());
+                self.collect_accepted(next, alphabet, remaining - 1, prefix, accepted);
+                prefix.pop();
+            }
+        }
+    }
+
+    /// Counts the sequences over an alphabet that the automata accepts,
+    /// without enumerating them
+    /// 
+    /// # Arguments
+    /// * `alphabet` - the symbols sequences are made of
+    /// * `max_len` - the maximum length of the sequences
+    /// 
+    /// # Returns
+    /// * the number of accepted sequences of length `<= max_len`,
+    ///   saturating at `u128::MAX`
+    /// 
+    /// # Examples
+    /// ```
+    /// use levenshtein_lite::LevenshteinAutomata;
+    /// let lda = LevenshteinAutomata::new("ab", 1);
+    /// assert!(lda.count_accepted(&['a', 'b'], 2) == 5);
+    /// ```
+    pub fn count_accepted(&self, alphabet: &[T], max_len: usize) -> u128 {
+        let mut alphabet = alphabet.to_vec();
+        alphabet.sort();
+        alphabet.dedup();
+        // group each state's successors, so the `Any` edge is
+        // weighted by the number of symbols that fall back to it
+        let successors = self.states()
+            .map(|state| {
+                let mut targets = HashMap::new();
+                for symbol in &alphabet {
+                    if let Some(next) = self.step(state, symbol) {
+                        *targets.entry(next).or_insert(0u128) += 1;
+                    }
+                }
+                targets.into_iter().collect::<Vec<(usize, u128)>>()
+            })
+            .collect::<Vec<Vec<(usize, u128)>>>();
+
+        // counts[state] is the number of accepted sequences of
+        // length <= len when starting from state
+        let mut counts = self.heads
+            .iter()
+            .map(|head| head.accepting as u128)
+            .collect::<Vec<u128>>();
+        for _ in 0..max_len {
+            counts = successors
+                .iter()
+                .zip(self.heads.iter())
+                .map(|(targets, head)| {
+                    targets.iter().fold(head.accepting as u128, |total, &(next, n)| {
+                        total.saturating_add(counts[next].saturating_mul(n))
+                    })
+                })
+                .collect();
+        }
+        counts[0]
+    }
+
+    /// Estimates the memory used by the automata, in bytes
+    /// 
+    /// Memory owned by the symbols themselves, e.g. the contents of
+    /// `String` symbols, is not included.
+    pub fn memory_usage(&self) -> usize {
+        size_of::<Self>()
+            + self.src.capacity() * size_of::<T>()
+            + self.src_text.get().map_or(0, |src| src.capacity())
+            + self.heads.capacity() * size_of::<Head>()
+            + self.transitions.capacity() * size_of::<Transition<T>>()
+    }
+}
+
+impl<T: Eq + Hash + Ord + Clone + Display> LevenshteinAutomata<T> {
+    /// Renders the automata in the Graphviz DOT language
+    /// 
+    /// States are numbered by their position in the automata, starting
+    /// at `0`; accepting states are drawn with a double circle. Edges
+    /// are labelled with the symbol they match; the fallback edge taken
+    /// by any other symbol is dashed and labelled `*`, so that it can't
+    /// be mistaken for an edge matching a literal `*`.
+    /// 
+    /// # Returns
+    /// * the DOT source of a directed graph
+    /// 
+    /// # Examples
+    /// ```
+    /// use levenshtein_lite::LevenshteinAutomata;
+    /// let lda = LevenshteinAutomata::new("a", 0);
+    /// assert!(lda.to_dot().contains("0 -> 1 [label=\"a\"];"));
+    /// ```
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph LevenshteinAutomata {\n");
+        dot.push_str("    rankdir=LR;\n");
+        dot.push_str("    start [shape=point];\n");
+        for (idx, head) in self.heads.iter().enumerate() {
+            let shape = if head.accepting { "doublecircle" } else { "circle" };
+            dot.push_str(&format!("    {} [shape={}];\n", idx, shape));
+        }
+        dot.push_str("    start -> 0;\n");
+        for (idx, head) in self.heads.iter().enumerate() {
+            if !head.has_children {
+                continue;
+            }
+            for trn in &self.transitions[head.trns_start..head.trns_end + 1] {
+                let attrs = match trn.tchar {
+                    // DOT strings only need quotes and backslashes escaped
+                    TChar::Char(ref c) => format!(
+                        "label=\"{}\"",
+                        c.to_string().replace('\\', "\\\\").replace('"', "\\\"")
+                    ),
+                    TChar::Any => "label=\"*\", style=dashed".to_string()
+                };
+                dot.push_str(&format!(
+                    "    {} -> {} [{}];\n",
+                    idx, trn.points_to, attrs
+                ));
+            }
+        }
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+
+/// Computes the Leveshtein distance between two input strings
+/// 
+/// # Arguments
+/// * `a` - a string
+/// * `b` - a string
+/// 
+/// # Returns
+/// * the Levenshtein distance between `a` and `b`
+/// 
+/// # Examples
+/// ```
+/// use levenshtein_lite::levenshtein_distance;
+/// assert!(levenshtein_distance("abc", "abx") == 1);
+/// assert!(levenshtein_distance("abc", "axx") == 2);
+/// ```
+pub fn levenshtein_distance(a: &str, b: &str) -> i32 {
+    let (rowstr, colstr) = (a.chars().collect::<Vec<char>>(), b);
+    let mut prev = (0..rowstr.len() as i32 + 1).collect::<Vec<i32>>();
+    let mut current = prev.clone();
+    for (uci, cchar) in colstr.chars().enumerate() {
+        current[0] = uci as i32 + 1;
+        for (uri, &rchar) in rowstr.iter().enumerate() {
+            let ri = uri + 1;
+            let r_insert_d = prev[ri] + 1;
+            let r_del_d = current[ri - 1] + 1;
             let r_match_or_sub_d =
                 if rchar == cchar { prev[ri - 1] } else { prev[ri - 1] + 1 };
             current[ri] = min(r_match_or_sub_d, min(r_insert_d, r_del_d));
@@ -371,6 +1286,189 @@ pub fn levenshtein_distance(a: &str, b: &str) -> i32 {
 }
 
 
+/// Computes the Levenshtein distance between two input strings,
+/// giving up as soon as it is known to exceed `max_distance`
+/// 
+/// Only the diagonal band of the matrix that can stay within
+/// `max_distance` is evaluated, so this is considerably faster
+/// than `levenshtein_distance` for small bounds.
+/// 
+/// # Arguments
+/// * `a` - a string
+/// * `b` - a string
+/// * `max_distance` - the largest distance of interest
+/// 
+/// # Returns
+/// * `Some(levenshtein_distance(a, b))` if it is at most `max_distance`,
+///   otherwise `None`
+/// 
+/// # Examples
+/// ```
+/// use levenshtein_lite::levenshtein_distance_bounded;
+/// assert!(levenshtein_distance_bounded("abc", "abx", 1) == Some(1));
+/// assert!(levenshtein_distance_bounded("abc", "axx", 1) == None);
+/// ```
+pub fn levenshtein_distance_bounded(a: &str, b: &str, max_distance: i32) -> Option<i32> {
+    let (rowstr, colstr) = (a.chars().collect::<Vec<char>>(), b);
+    let rlen = rowstr.len();
+    if max_distance < 0 || (rlen as i32 - colstr.chars().count() as i32).abs() > max_distance {
+        return None;
+    }
+    let band = max_distance as usize;
+    // any value above max_distance is as good as another,
+    // so cells outside the band all hold this
+    let over = max_distance + 1;
+    let mut prev = (0..rlen as i32 + 1).map(|d| min(d, over)).collect::<Vec<i32>>();
+    let mut current = vec![over; rlen + 1];
+    for (uci, cchar) in colstr.chars().enumerate() {
+        let ci = uci + 1;
+        let (lo, hi) = (ci.saturating_sub(band).max(1), min(rlen, ci + band));
+        current[0] = min(ci as i32, over);
+        if lo > 1 {
+            current[lo - 1] = over;
+        }
+        let mut row_min = current[0];
+        for ri in lo..hi + 1 {
+            let rchar = rowstr[ri - 1];
+            let r_insert_d = prev[ri] + 1;
+            let r_del_d = current[ri - 1] + 1;
+            let r_match_or_sub_d =
+                if rchar == cchar { prev[ri - 1] } else { prev[ri - 1] + 1 };
+            current[ri] = min(over, min(r_match_or_sub_d, min(r_insert_d, r_del_d)));
+            row_min = min(row_min, current[ri]);
+        }
+        if hi < rlen {
+            current[hi + 1] = over;
+        }
+        if row_min > max_distance {
+            // every path through this row already exceeds the bound
+            return None;
+        }
+        (current, prev) = (prev, current);
+    }
+    let distance = prev[rlen];
+    if distance <= max_distance { Some(distance) } else { None }
+}
+
+/// Computes the optimal string alignment distance between two input strings
+///
+/// This is the Levenshtein distance extended with transpositions of
+/// two adjacent chars, under the restriction that no substring is
+/// edited more than once. Because of that restriction it does not
+/// satisfy the triangle inequality, see `damerau_levenshtein_distance`.
+///
+/// # Arguments
+/// * `a` - a string
+/// * `b` - a string
+///
+/// # Returns
+/// * the optimal string alignment distance between `a` and `b`
+///
+/// # Examples
+/// ```
+/// use levenshtein_lite::osa_distance;
+/// assert!(osa_distance("abc", "acb") == 1);
+/// assert!(osa_distance("CA", "ABC") == 3);
+/// ```
+pub fn osa_distance(a: &str, b: &str) -> i32 {
+    let (rowstr, colstr) = (
+        a.chars().collect::<Vec<char>>(),
+        b.chars().collect::<Vec<char>>()
+    );
+    let mut prev_prev = vec![0; rowstr.len() + 1];
+    let mut prev = (0..rowstr.len() as i32 + 1).collect::<Vec<i32>>();
+    let mut current = prev.clone();
+    for (uci, &cchar) in colstr.iter().enumerate() {
+        current[0] = uci as i32 + 1;
+        for (uri, &rchar) in rowstr.iter().enumerate() {
+            let ri = uri + 1;
+            let r_insert_d = prev[ri] + 1;
+            let r_del_d = current[ri - 1] + 1;
+            let r_match_or_sub_d =
+                if rchar == cchar { prev[ri - 1] } else { prev[ri - 1] + 1 };
+            current[ri] = min(r_match_or_sub_d, min(r_insert_d, r_del_d));
+            if uri > 0 && uci > 0
+                && rchar == colstr[uci - 1]
+                && rowstr[uri - 1] == cchar {
+                // adjacent transposition
+                current[ri] = min(current[ri], prev_prev[ri - 2] + 1);
+            }
+        }
+        // rotate the rows, so that current can be overwritten
+        (prev_prev, prev, current) = (prev, current, prev_prev);
+    }
+    prev[prev.len() - 1]
+}
+
+/// Computes the (unrestricted) Damerau-Levenshtein distance between two input strings
+///
+/// Unlike `osa_distance`, substrings may be edited after being transposed,
+/// which makes this a true metric.
+///
+/// # Arguments
+/// * `a` - a string
+/// * `b` - a string
+///
+/// # Returns
+/// * the Damerau-Levenshtein distance between `a` and `b`
+///
+/// # Examples
+/// ```
+/// use levenshtein_lite::damerau_levenshtein_distance;
+/// assert!(damerau_levenshtein_distance("abc", "acb") == 1);
+/// assert!(damerau_levenshtein_distance("CA", "ABC") == 2);
+/// ```
+pub fn damerau_levenshtein_distance(a: &str, b: &str) -> i32 {
+    let (rowstr, colstr) = (
+        a.chars().collect::<Vec<char>>(),
+        b.chars().collect::<Vec<char>>()
+    );
+    let (rlen, clen) = (rowstr.len(), colstr.len());
+    let max_d = (rlen + clen) as i32;
+    // the matrix has an extra leading row and column holding `max_d`,
+    // so that transpositions reaching past the start are never chosen
+    let width = clen + 2;
+    let mut d = vec![0; (rlen + 2) * width];
+    d[0] = max_d;
+    for ri in 0..rlen + 1 {
+        d[(ri + 1) * width] = max_d;
+        d[(ri + 1) * width + 1] = ri as i32;
+    }
+    for ci in 0..clen + 1 {
+        d[ci + 1] = max_d;
+        d[width + ci + 1] = ci as i32;
+    }
+    // last row in which each char was seen
+    let mut last_row: HashMap<char, usize> = HashMap::new();
+    for ri in 1..rlen + 1 {
+        // last column in this row where the chars matched
+        let mut last_match_col = 0;
+        for ci in 1..clen + 1 {
+            let k = *last_row.get(&colstr[ci - 1]).unwrap_or(&0);
+            let l = last_match_col;
+            let cost = if rowstr[ri - 1] == colstr[ci - 1] {
+                last_match_col = ci;
+                0
+            } else {
+                1
+            };
+            let r_match_or_sub_d = d[ri * width + ci] + cost;
+            let r_insert_d = d[(ri + 1) * width + ci] + 1;
+            let r_del_d = d[ri * width + ci + 1] + 1;
+            // transpose the chars at k and ri, editing everything in between
+            let r_trans_d = d[k * width + l]
+                + (ri - k - 1) as i32 + 1 + (ci - l - 1) as i32;
+            d[(ri + 1) * width + ci + 1] = min(
+                min(r_match_or_sub_d, r_trans_d),
+                min(r_insert_d, r_del_d)
+            );
+        }
+        last_row.insert(rowstr[ri - 1], ri);
+    }
+    d[(rlen + 1) * width + clen + 1]
+}
+
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -400,6 +1498,303 @@ mod test {
         }
     }
 
+    #[test]
+    fn exhaustive_automata() {
+        // inputs drawn from the source's own characters exercise
+        // the interplay between specific transitions and ANY
+        let test_strings = vec!["", "a", "ab", "aab", "abc", "abca", "thier"];
+        for test_str in test_strings {
+            let mut alphabet = test_str.chars().collect::<Vec<char>>();
+            alphabet.push('x');
+            for lda_d in 0..3 {
+                let lda = LevenshteinAutomata::new(test_str, lda_d);
+                let max_len = test_str.chars().count() + lda_d as usize + 1;
+                if let Err(counterexample) = lda.verify_against_dp(&alphabet, max_len) {
+                    panic!("'{}' at distance {}: {}", test_str, lda_d, counterexample);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn distance_range_automata() {
+        for test_str in ["", "a", "ab", "aab", "abca"] {
+            let mut alphabet = test_str.chars().collect::<Vec<char>>();
+            alphabet.push('x');
+            for (min_d, max_d) in [(1, 1), (1, 2), (2, 2), (0, 2), (2, 3), (3, 3)] {
+                let lda = LevenshteinAutomata::with_distance_range(test_str, min_d..=max_d);
+                assert!(lda.min_distance() == min_d && lda.max_distance() == max_d);
+                let max_len = test_str.chars().count() + max_d as usize;
+                if let Err(counterexample) = lda.verify_against_dp(&alphabet, max_len) {
+                    panic!("'{}' at distances {}..={}: {}", test_str, min_d, max_d, counterexample);
+                }
+            }
+        }
+        // the full range is the plain automata
+        assert!(LevenshteinAutomata::with_distance_range("abc", 0..=2) == LevenshteinAutomata::new("abc", 2));
+    }
+
+    #[test]
+    fn verify_match_modes() {
+        for mode in [MatchMode::Full, MatchMode::Suffix, MatchMode::Infix] {
+            for test_str in ["", "a", "ab", "aba", "café"] {
+                let mut alphabet = test_str.chars().collect::<Vec<char>>();
+                alphabet.push('x');
+                for d in 0..=2 {
+                    let lda = LevenshteinAutomata::with_match_mode(test_str, d, mode);
+                    if let Err(counterexample) = lda.verify_against_dp(&alphabet, 6) {
+                        panic!("'{}' at distance {} in {:?}: {}", test_str, d, mode, counterexample);
+                    }
+                }
+            }
+        }
+        // a wrong automata is still caught, by the distance of the closest part
+        let suffix = LevenshteinAutomata::with_match_mode("ab", 0, MatchMode::Suffix);
+        let infix = LevenshteinAutomata::with_match_mode("ab", 0, MatchMode::Infix);
+        let mut mismatched = suffix.clone();
+        mismatched.mode = MatchMode::Infix;
+        assert!(suffix.verify_against_dp(&['a', 'b', 'x'], 3).is_ok());
+        assert!(infix.verify_against_dp(&['a', 'b', 'x'], 3).is_ok());
+        let err = mismatched.verify_against_dp(&['a', 'b', 'x'], 3).unwrap_err();
+        assert!(err == Counterexample { input: "aba".to_string(), distance: 0, accepted: false });
+    }
+
+    #[test]
+    fn match_modes() {
+        for src in ["", "a", "ab", "aba", "abba"] {
+            for d in 0..=2 {
+                let full = LevenshteinAutomata::with_match_mode(src, d, MatchMode::Full);
+                assert!(full == LevenshteinAutomata::new(src, d));
+                for mode in [MatchMode::Full, MatchMode::Suffix, MatchMode::Infix] {
+                    let lda = LevenshteinAutomata::with_match_mode(src, d, mode);
+                    assert!(lda.match_mode() == mode);
+                    // the span found is at exactly the closest distance,
+                    // which is checked one distance at a time
+                    for k in 0..=d {
+                        let found_at_k = |input: &str| lda.best_span(input).is_some_and(|span| {
+                            let part = &input[span.start..span.end];
+                            span.distance == k as i32 && levenshtein_distance(src, part) == span.distance
+                        });
+                        let distance_to = |input: &str| closest_part_distance(src, input, mode);
+                        let checked = verify::verify_with(k..=k, &['a', 'b', 'x'], 6, distance_to, found_at_k);
+                        assert!(checked.is_ok(), "'{}' at distance {} in {:?}: {:?}", src, k, mode, checked);
+                    }
+                }
+            }
+        }
+
+        // spans are in bytes, leftmost then longest
+        let lda = LevenshteinAutomata::with_match_mode("café", 1, MatchMode::Infix);
+        assert!(lda.best_span("un cafe, deux cafes") == Some(FuzzySpan { start: 3, end: 7, distance: 1 }));
+        let input = "un cafe, deux cafés";
+        assert!(lda.best_span(input) == Some(FuzzySpan { start: 14, end: 19, distance: 0 }));
+        let lda = LevenshteinAutomata::with_match_mode("café", 1, MatchMode::Suffix);
+        assert!(lda.best_span(input) == Some(FuzzySpan { start: 14, end: 20, distance: 1 }));
+    }
+
+    #[test]
+    fn dot_export() {
+        let lda = LevenshteinAutomata::new("a\"", 0);
+        let expected = vec![
+            "digraph LevenshteinAutomata {",
+            "    rankdir=LR;",
+            "    start [shape=point];",
+            "    0 [shape=circle];",
+            "    1 [shape=circle];",
+            "    2 [shape=doublecircle];",
+            "    start -> 0;",
+            "    0 -> 1 [label=\"a\"];",
+            "    1 -> 2 [label=\"\\\"\"];",
+            "}",
+            ""
+        ];
+        assert!(lda.to_dot() == expected.join("\n"));
+
+        // non-ASCII symbols are drawn as themselves
+        let lda = LevenshteinAutomata::new("é\\", 0);
+        assert!(lda.to_dot().contains("0 -> 1 [label=\"é\"];"));
+        assert!(lda.to_dot().contains("1 -> 2 [label=\"\\\\\"];"));
+
+        let lda = LevenshteinAutomata::new("", 1);
+        assert!(lda.to_dot().contains("0 [shape=doublecircle];"));
+        assert!(lda.to_dot().contains("0 -> 1 [label=\"*\", style=dashed];"));
+
+        // a literal '*' is drawn apart from the fallback edge
+        let lda = LevenshteinAutomata::new("a*", 0);
+        assert!(lda.to_dot().contains("1 -> 2 [label=\"*\"];"));
+        assert!(!lda.to_dot().contains("dashed"));
+        let dot = LevenshteinAutomata::new("a*", 1).to_dot();
+        let literal = dot.lines().filter(|line| line.ends_with("[label=\"*\"];")).count();
+        let fallback = dot.lines().filter(|line| line.ends_with("[label=\"*\", style=dashed];")).count();
+        assert!(literal > 0 && fallback > 0);
+    }
+
+    #[test]
+    fn introspection() {
+        let lda = LevenshteinAutomata::new("abc", 1);
+        assert!(lda.states().count() == lda.num_states());
+        assert!(lda.states().map(|s| lda.edges(s).count()).sum::<usize>() == lda.num_transitions());
+        assert!(lda.accepting_states().count() > 0);
+        assert!(lda.memory_usage() > lda.num_states() * size_of::<Head>());
+
+        // walking the edges by hand agrees with check
+        let walk = |input: &str| {
+            let mut state = 0;
+            for c in input.chars() {
+                let mut edges = lda.edges(state).collect::<Vec<(Label, usize)>>();
+                edges.sort();
+                match edges.iter().find(|&&(label, _)| label == Label::Char(c) || label == Label::Any) {
+                    Some(&(_, target)) => state = target,
+                    None => return false
+                }
+            }
+            lda.is_accepting(state)
+        };
+        for input in ["abc", "ab", "abcd", "xbc", "axx", "", "cba", "aabc"] {
+            assert!(walk(input) == lda.check(input));
+        }
+    }
+
+    #[test]
+    fn canonical_layout() {
+        use std::collections::hash_map::DefaultHasher;
+        use std::hash::Hasher;
+        let hash = |lda: &LevenshteinAutomata| {
+            let mut hasher = DefaultHasher::new();
+            lda.hash(&mut hasher);
+            hasher.finish()
+        };
+        for (src, d) in [("", 1), ("abc", 1), ("levenshtein", 2), ("aabbaa", 3)] {
+            let lda = LevenshteinAutomata::new(src, d);
+            for _ in 0..5 {
+                // each build iterates its hashmaps in a different order
+                let other = LevenshteinAutomata::new(src, d);
+                assert!(other == lda && hash(&other) == hash(&lda));
+                assert!(other.to_dot() == lda.to_dot());
+            }
+            assert!(lda.clone() == lda);
+            assert!(lda != LevenshteinAutomata::new(src, d + 1));
+
+            // states are numbered in the order they are first reached
+            let mut next_new = 1;
+            for state in lda.states() {
+                let edges = lda.edges(state).collect::<Vec<(Label, usize)>>();
+                assert!(edges.windows(2).all(|pair| pair[0].0 < pair[1].0));
+                for (_, target) in edges {
+                    if target == next_new {
+                        next_new += 1;
+                    }
+                    assert!(target < next_new);
+                }
+            }
+        }
+        assert!(format!("{:?}", LevenshteinAutomata::new("a", 0)).starts_with("LevenshteinAutomata {"));
+
+        // automata built from chars are the same automata, whatever the constructor
+        for (src, d) in [("", 0), ("abc", 1), ("café", 2)] {
+            let lda = LevenshteinAutomata::new(src, d);
+            let from_chars = LevenshteinAutomata::from_symbols(src.chars(), d);
+            assert!(from_chars == lda && hash(&from_chars) == hash(&lda));
+            assert!(from_chars.details() == (src, d) && lda.details() == (src, d));
+            // filling in the text doesn't change equality, hashes or output
+            assert!(from_chars == LevenshteinAutomata::from_symbols(src.chars(), d));
+            assert!(format!("{:?}", from_chars) == format!("{:?}", LevenshteinAutomata::new(src, d)));
+        }
+    }
+
+    #[test]
+    fn encoded_inputs() {
+        let lda = LevenshteinAutomata::new("naïve café", 2);
+        for input in ["naïve café", "naive cafe", "naïve", "náïvé cafè", "", "💥💥"] {
+            let expected = lda.check(input);
+            assert!(lda.check_chars(input.chars().collect::<Vec<char>>()) == expected);
+            let utf16 = input.encode_utf16().collect::<Vec<u16>>();
+            assert!(lda.check_utf16(&utf16).unwrap() == expected);
+            assert!(lda.check_reader(input.as_bytes()).unwrap() == expected);
+        }
+
+        // one byte per read, so chars are split across reads
+        struct ByteReader<'a>(&'a [u8]);
+        impl Read for ByteReader<'_> {
+            fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+                match self.0.split_first() {
+                    Some((&byte, rest)) if !buf.is_empty() => {
+                        buf[0] = byte;
+                        self.0 = rest;
+                        Ok(1)
+                    },
+                    _ => Ok(0)
+                }
+            }
+        }
+        assert!(lda.check_reader(ByteReader("naïve cafè".as_bytes())).unwrap());
+        assert!(!lda.check_reader(ByteReader("naïve 💥💥💥".as_bytes())).unwrap());
+
+        let err = lda.check_utf16(&[0x6E, 0xD83D, 0xDCA5, 0xDC00]).unwrap_err();
+        assert!(matches!(err, CheckError::InvalidUtf16 { position: 3 }));
+        let err = lda.check_reader(&b"na\xC3\xAFve\xC3"[..]).unwrap_err();
+        assert!(matches!(err, CheckError::InvalidUtf8 { offset: 6 }));
+        assert!(err.to_string() == "invalid UTF-8 at byte offset 6");
+        let err = lda.check_reader(ByteReader(b"na\xE2\x28ve")).unwrap_err();
+        assert!(matches!(err, CheckError::InvalidUtf8 { offset: 2 }));
+        // nothing past a certain mismatch is decoded
+        assert!(!lda.check_reader(&b"xxxxx\xFF"[..]).unwrap());
+
+        struct FailingReader;
+        impl Read for FailingReader {
+            fn read(&mut self, _: &mut [u8]) -> io::Result<usize> {
+                Err(io::Error::other("disk on fire"))
+            }
+        }
+        let err = lda.check_reader(FailingReader).unwrap_err();
+        assert!(matches!(err, CheckError::Io(_)) && err.source().is_some());
+    }
+
+    #[test]
+    fn generic_symbols() {
+        let phrase = "the quick brown fox".split(' ').collect::<Vec<&str>>();
+        let lda = LevenshteinAutomata::from_symbols(phrase.clone(), 1);
+        assert!(lda.symbols() == phrase.as_slice() && lda.max_distance() == 1);
+        assert!(lda.check_symbols("the quick brown fox".split(' ')));
+        assert!(lda.check_symbols("the quick fox".split(' ')));
+        assert!(lda.check_symbols("the slow brown fox".split(' ')));
+        assert!(lda.check_symbols("the quick brown fox jumps".split(' ')));
+        assert!(!lda.check_symbols("a slow brown fox".split(' ')));
+        assert!(!lda.check_symbols("quick the fox brown".split(' ')));
+
+        // symbols agree with the char automata on the same sequence
+        let opcodes = LevenshteinAutomata::from_symbols([1u8, 2, 3, 4], 1);
+        let chars = LevenshteinAutomata::new("abcd", 1);
+        for input in ["abcd", "abd", "abxd", "bcd", "abdc", "xbcy", ""] {
+            let bytes = input.bytes().map(|b| b - b'a' + 1);
+            assert!(opcodes.check_symbols(bytes) == chars.check(input));
+        }
+    }
+
+    #[test]
+    fn enumerate_accepted() {
+        let lda = LevenshteinAutomata::new("abc", 1);
+        let alphabet = ['c', 'x', 'a', 'b', 'a'];
+        let accepted = lda.accepted_strings(&alphabet, 5);
+
+        let mut expected = verify::all_strings(&['a', 'b', 'c', 'x'], 5);
+        expected.retain(|s| levenshtein_distance("abc", s) <= 1);
+        expected.sort();
+
+        let accepted = accepted
+            .into_iter()
+            .map(|s| s.into_iter().collect::<String>())
+            .collect::<Vec<String>>();
+        assert!(accepted == expected);
+        assert!(lda.count_accepted(&alphabet, 5) == expected.len() as u128);
+
+        // counting scales past what could be enumerated
+        let lda = LevenshteinAutomata::new("", 2);
+        let alphabet = ('a'..='z').collect::<Vec<char>>();
+        assert!(lda.count_accepted(&alphabet, 2) == 1 + 26 + 26 * 26);
+        assert!(LevenshteinAutomata::new("abc", 1).count_accepted(&alphabet, 0) == 0);
+    }
+
     #[test]
     fn basic_distance() {
         let pairs = vec![
@@ -431,6 +1826,68 @@ mod test {
         }
     }
 
+    #[test]
+    fn bounded_distance() {
+        let mut rng = rand::thread_rng();
+        for _ in 0..5000 {
+            let a = (0..rng.gen_range(0..10))
+                .map(|_| ['a', 'b', 'é'][rng.gen_range(0..3)])
+                .collect::<String>();
+            let b = (0..rng.gen_range(0..10))
+                .map(|_| ['a', 'b', 'é'][rng.gen_range(0..3)])
+                .collect::<String>();
+            let max_distance = rng.gen_range(-1..8);
+            let d = levenshtein_distance(&a, &b);
+            let expected = if d <= max_distance { Some(d) } else { None };
+            assert!(levenshtein_distance_bounded(&a, &b, max_distance) == expected);
+        }
+    }
+
+    #[test]
+    fn transposition_distances() {
+        // (a, b, levenshtein, osa, damerau-levenshtein)
+        let cases = vec![
+            ("", "", 0, 0, 0),
+            ("abc", "", 3, 3, 3),
+            ("abc", "abc", 0, 0, 0),
+            ("abc", "acb", 2, 1, 1),
+            ("abcd", "badc", 3, 2, 2),
+            ("ca", "ac", 2, 1, 1),
+            // OSA may not edit the transposed pair again,
+            // so only the unrestricted variant finds CA -> AC -> ABC
+            ("CA", "ABC", 3, 3, 2),
+            ("ABC", "CA", 3, 3, 2),
+            ("a cat", "an act", 3, 2, 2),
+            ("kitten", "sitting", 3, 3, 3)
+        ];
+
+        for (a, b, lev, osa, dl) in cases {
+            println!("Comparing '{}' with '{}'", a, b);
+            assert!(levenshtein_distance(a, b) == lev);
+            assert!(osa_distance(a, b) == osa);
+            assert!(damerau_levenshtein_distance(a, b) == dl);
+        }
+
+        // on random strings, each variant can only improve on the last
+        let mut rng = rand::thread_rng();
+        for _ in 0..2000 {
+            let a = (0..rng.gen_range(0..8))
+                .map(|_| ['a', 'b', 'c'][rng.gen_range(0..3)])
+                .collect::<String>();
+            let b = (0..rng.gen_range(0..8))
+                .map(|_| ['a', 'b', 'c'][rng.gen_range(0..3)])
+                .collect::<String>();
+            let (lev, osa, dl) = (
+                levenshtein_distance(&a, &b),
+                osa_distance(&a, &b),
+                damerau_levenshtein_distance(&a, &b)
+            );
+            assert!(dl <= osa && osa <= lev);
+            assert!(osa == osa_distance(&b, &a));
+            assert!(dl == damerau_levenshtein_distance(&b, &a));
+        }
+    }
+
     #[test]
     fn fuzz_automata() {
         let mut rng = rand::thread_rng();
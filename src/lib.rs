@@ -1,6 +1,75 @@
 pub mod levenshtein_automata;
+pub mod lazy_automata;
+pub mod bit_parallel;
+pub mod approx_regex;
+pub mod verify;
+pub mod cache;
+pub mod dfa;
+pub mod edit_script;
+pub mod metrics;
+pub mod fuzziness;
+pub mod batch;
+pub mod top_k;
+pub mod bk_tree;
+pub mod symspell;
+pub mod suggester;
+pub mod did_you_mean;
+pub mod fuzzy_parse;
 
 pub use levenshtein_automata::{
     LevenshteinAutomata,
-    levenshtein_distance
+    Label,
+    MatchMode,
+    FuzzySpan,
+    CheckError,
+    levenshtein_distance,
+    levenshtein_distance_bounded,
+    osa_distance,
+    damerau_levenshtein_distance
 };
+pub use metrics::normalized_levenshtein;
+pub use lazy_automata::{
+    LazyLevenshteinAutomata,
+    LazyState
+};
+pub use bit_parallel::{
+    BitParallelMatcher,
+    LevenshteinMatcher
+};
+pub use approx_regex::{
+    ApproxRegex,
+    ParseRegexError
+};
+pub use cache::{
+    AutomataCache,
+    CacheStats
+};
+pub use dfa::Dfa;
+pub use edit_script::{
+    EditOp,
+    edit_script,
+    alignment
+};
+pub use fuzziness::Fuzziness;
+pub use batch::{
+    DistanceCalculator,
+    levenshtein_distance_many
+};
+pub use top_k::{
+    Neighbour,
+    TieBreak,
+    top_k
+};
+pub use bk_tree::BkTree;
+pub use symspell::{
+    SymSpellIndex,
+    SymSpellStats
+};
+pub use suggester::{
+    Suggester,
+    SuggestOptions,
+    Suggestion
+};
+
+#[cfg(feature = "derive")]
+pub use levenshtein_lite_derive::FuzzyFromStr;
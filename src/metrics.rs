@@ -0,0 +1,263 @@
+use std::cmp::{max, min};
+use crate::levenshtein_distance;
+
+/// Normalizes the Levenshtein distance between two strings into a similarity
+///
+/// # Returns
+/// * `1 - levenshtein_distance / max(len(a), len(b))`;
+///   two empty strings are considered identical
+///
+/// # Examples
+/// ```
+/// use levenshtein_lite::metrics::normalized_levenshtein;
+/// assert!(normalized_levenshtein("abcd", "abcx") == 0.75);
+/// assert!(normalized_levenshtein("", "") == 1.0);
+/// ```
+pub fn normalized_levenshtein(a: &str, b: &str) -> f64 {
+    let len = max(a.chars().count(), b.chars().count());
+    if len == 0 {
+        return 1.0;
+    }
+    1.0 - levenshtein_distance(a, b) as f64 / len as f64
+}
+
+/// Computes the Hamming distance between two input strings
+///
+/// # Arguments
+/// * `a` - a string
+/// * `b` - a string
+///
+/// # Returns
+/// * the number of positions at which the chars of `a` and `b` differ,
+///   or `None` if they are not the same number of chars long
+///
+/// # Examples
+/// ```
+/// use levenshtein_lite::metrics::hamming_distance;
+/// assert!(hamming_distance("abc", "abx") == Some(1));
+/// assert!(hamming_distance("abc", "ab") == None);
+/// ```
+pub fn hamming_distance(a: &str, b: &str) -> Option<i32> {
+    let (mut a_iter, mut b_iter) = (a.chars(), b.chars());
+    let mut distance = 0;
+    loop {
+        match (a_iter.next(), b_iter.next()) {
+            (Some(ac), Some(bc)) => if ac != bc {
+                distance += 1;
+            },
+            (None, None) => return Some(distance),
+            _ => return None
+        }
+    }
+}
+
+/// Normalizes the Hamming distance between two strings into a similarity
+///
+/// # Returns
+/// * `1 - distance / length`, or `None` if the strings
+///   are not the same number of chars long;
+///   two empty strings are considered identical
+///
+/// # Examples
+/// ```
+/// use levenshtein_lite::metrics::normalized_hamming;
+/// assert!(normalized_hamming("abcd", "abxx") == Some(0.5));
+/// ```
+pub fn normalized_hamming(a: &str, b: &str) -> Option<f64> {
+    let distance = hamming_distance(a, b)?;
+    let len = a.chars().count();
+    if len == 0 {
+        return Some(1.0);
+    }
+    Some(1.0 - distance as f64 / len as f64)
+}
+
+/// Computes the Jaro similarity between two input strings
+///
+/// # Returns
+/// * a similarity in `[0, 1]`, where `1` means the strings are identical;
+///   two empty strings are considered identical
+///
+/// # Examples
+/// ```
+/// use levenshtein_lite::metrics::jaro;
+/// assert!((jaro("martha", "marhta") - 0.944).abs() < 0.001);
+/// assert!(jaro("abc", "xyz") == 0.0);
+/// ```
+pub fn jaro(a: &str, b: &str) -> f64 {
+    let (a, b) = (
+        a.chars().collect::<Vec<char>>(),
+        b.chars().collect::<Vec<char>>()
+    );
+    if a.is_empty() && b.is_empty() {
+        return 1.0;
+    }
+    if a.is_empty() || b.is_empty() {
+        return 0.0;
+    }
+
+    // chars only count as matching if they are no further apart than this
+    let window = (max(a.len(), b.len()) / 2).saturating_sub(1);
+    let mut a_matched = vec![false; a.len()];
+    let mut b_matched = vec![false; b.len()];
+    let mut matches = 0;
+    for (ai, &ac) in a.iter().enumerate() {
+        let start = ai.saturating_sub(window);
+        let end = min(ai + window + 1, b.len());
+        for bi in start..end {
+            if !b_matched[bi] && b[bi] == ac {
+                a_matched[ai] = true;
+                b_matched[bi] = true;
+                matches += 1;
+                break;
+            }
+        }
+    }
+    if matches == 0 {
+        return 0.0;
+    }
+
+    // matching chars that appear in a different order are transpositions
+    let a_seq = a.iter().zip(a_matched).filter(|(_, m)| *m).map(|(c, _)| c);
+    let b_seq = b.iter().zip(b_matched).filter(|(_, m)| *m).map(|(c, _)| c);
+    let half_transpositions = a_seq.zip(b_seq).filter(|(ac, bc)| ac != bc).count();
+
+    let m = matches as f64;
+    let t = (half_transpositions / 2) as f64;
+    (m / a.len() as f64 + m / b.len() as f64 + (m - t) / m) / 3.0
+}
+
+/// Computes the Jaro-Winkler similarity between two input strings
+///
+/// This is the Jaro similarity, boosted for strings that share a common
+/// prefix of up to 4 chars, using the conventional scaling factor of `0.1`.
+///
+/// # Returns
+/// * a similarity in `[0, 1]`, where `1` means the strings are identical
+///
+/// # Examples
+/// ```
+/// use levenshtein_lite::metrics::jaro_winkler;
+/// assert!((jaro_winkler("martha", "marhta") - 0.961).abs() < 0.001);
+/// ```
+pub fn jaro_winkler(a: &str, b: &str) -> f64 {
+    let sim = jaro(a, b);
+    let prefix = a.chars()
+        .zip(b.chars())
+        .take(4)
+        .take_while(|(ac, bc)| ac == bc)
+        .count();
+    sim + prefix as f64 * 0.1 * (1.0 - sim)
+}
+
+/// Computes the length of the longest common subsequence of two input strings
+///
+/// # Returns
+/// * the number of chars in the longest common subsequence
+///
+/// # Examples
+/// ```
+/// use levenshtein_lite::metrics::lcs_length;
+/// assert!(lcs_length("abcde", "ace") == 3);
+/// ```
+pub fn lcs_length(a: &str, b: &str) -> i32 {
+    let rowstr = a.chars().collect::<Vec<char>>();
+    let mut prev = vec![0; rowstr.len() + 1];
+    let mut current = prev.clone();
+    for cchar in b.chars() {
+        for (uri, &rchar) in rowstr.iter().enumerate() {
+            let ri = uri + 1;
+            current[ri] = if rchar == cchar {
+                prev[ri - 1] + 1
+            } else {
+                max(prev[ri], current[ri - 1])
+            };
+        }
+        (current, prev) = (prev, current);
+    }
+    prev[rowstr.len()]
+}
+
+/// Computes the indel distance between two input strings, i.e. the
+/// Levenshtein distance when only insertions and deletions are allowed
+///
+/// # Returns
+/// * `len(a) + len(b) - 2 * lcs_length(a, b)`, counted in chars
+///
+/// # Examples
+/// ```
+/// use levenshtein_lite::metrics::indel_distance;
+/// assert!(indel_distance("abc", "abx") == 2);
+/// ```
+pub fn indel_distance(a: &str, b: &str) -> i32 {
+    let total = (a.chars().count() + b.chars().count()) as i32;
+    total - 2 * lcs_length(a, b)
+}
+
+/// Normalizes the indel distance between two strings into a similarity
+///
+/// # Returns
+/// * `1 - indel_distance / (len(a) + len(b))`;
+///   two empty strings are considered identical
+///
+/// # Examples
+/// ```
+/// use levenshtein_lite::metrics::normalized_indel;
+/// assert!(normalized_indel("abcd", "abxx") == 0.5);
+/// ```
+pub fn normalized_indel(a: &str, b: &str) -> f64 {
+    let total = a.chars().count() + b.chars().count();
+    if total == 0 {
+        return 1.0;
+    }
+    1.0 - indel_distance(a, b) as f64 / total as f64
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn close(x: f64, y: f64) -> bool {
+        (x - y).abs() < 0.001
+    }
+
+    #[test]
+    fn basic_metrics() {
+        assert!(hamming_distance("", "") == Some(0));
+        assert!(hamming_distance("karolin", "kathrin") == Some(3));
+        assert!(hamming_distance("abc", "abcd").is_none());
+        assert!(normalized_hamming("", "") == Some(1.0));
+
+        assert!(normalized_levenshtein("abc", "") == 0.0);
+        assert!(close(normalized_levenshtein("kitten", "sitting"), 4.0 / 7.0));
+
+        assert!(jaro("", "") == 1.0);
+        assert!(jaro("abc", "") == 0.0);
+        assert!(close(jaro("dixon", "dicksonx"), 0.767));
+        assert!(close(jaro_winkler("dixon", "dicksonx"), 0.813));
+        assert!(close(jaro_winkler("dwayne", "duane"), 0.84));
+        assert!(jaro_winkler("abc", "abc") == 1.0);
+
+        assert!(lcs_length("", "abc") == 0);
+        assert!(lcs_length("AGGTAB", "GXTXAYB") == 4);
+        assert!(indel_distance("kitten", "sitting") == 5);
+        assert!(normalized_indel("", "") == 1.0);
+        assert!(normalized_indel("abc", "xyz") == 0.0);
+    }
+
+    #[test]
+    fn unicode_metrics() {
+        // every metric counts chars, never bytes
+        assert!(hamming_distance("héllo", "hello") == Some(1));
+        assert!(normalized_hamming("日本", "日本") == Some(1.0));
+        assert!(jaro("naïve", "naïve") == 1.0);
+        assert!(close(jaro_winkler("café", "cafe"), jaro_winkler("cafx", "cafe")));
+        assert!(lcs_length("日本語", "日語") == 2);
+        assert!(indel_distance("日本語", "日語") == 1);
+        assert!(levenshtein_distance("日本語", "日語") == 1);
+        assert!(levenshtein_distance("é", "e") == 1);
+        assert!(levenshtein_distance("e", "é") == 1);
+        assert!(close(normalized_levenshtein("日本語", "日語"), 2.0 / 3.0));
+    }
+}
@@ -0,0 +1,202 @@
+use std::collections::HashMap;
+use crate::{LevenshteinAutomata, levenshtein_distance};
+
+/// Controls which suggestions a `Suggester` returns
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SuggestOptions {
+    /// the maximum Levenshtein Distance of a suggestion from the input
+    pub max_distance: i8,
+    /// the maximum number of suggestions to return
+    pub max_suggestions: usize,
+    /// if `true`, inputs that are in the dictionary get no suggestions
+    pub skip_known: bool
+}
+
+impl Default for SuggestOptions {
+    fn default() -> Self {
+        SuggestOptions {
+            max_distance: 2,
+            max_suggestions: 5,
+            skip_known: true
+        }
+    }
+}
+
+/// A spelling suggestion returned by a `Suggester`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Suggestion<'a> {
+    /// the suggested dictionary term
+    pub term: &'a str,
+    /// the Levenshtein distance between the input and the term
+    pub distance: i32,
+    /// the frequency of the term in the dictionary
+    pub frequency: u64
+}
+
+struct Entry {
+    term: String,
+    len: usize,
+    frequency: u64
+}
+
+/// A "did you mean" spelling corrector over a dictionary of term frequencies
+///
+/// Suggestions are the dictionary terms accepted by a `LevenshteinAutomata`
+/// built for the input, ranked by distance first, then by descending
+/// frequency, then alphabetically.
+pub struct Suggester {
+    options: SuggestOptions,
+    entries: Vec<Entry>,
+    positions: HashMap<String, usize>
+}
+
+impl Suggester {
+    /// Instantiates a new suggester with an empty dictionary
+    ///
+    /// # Arguments
+    /// * `options` - which suggestions to return
+    ///
+    /// # Returns
+    /// * A new `Suggester` instance
+    ///
+    /// # Examples
+    /// ```
+    /// use levenshtein_lite::{Suggester, SuggestOptions};
+    /// let mut suggester = Suggester::new(SuggestOptions::default());
+    /// suggester.insert("their", 500);
+    /// suggester.insert("there", 800);
+    /// suggester.insert("three", 300);
+    /// let terms = suggester.suggest("thier")
+    ///     .into_iter()
+    ///     .map(|s| s.term)
+    ///     .collect::<Vec<&str>>();
+    /// assert!(terms == vec!["there", "their", "three"]);
+    /// ```
+    pub fn new(options: SuggestOptions) -> Self {
+        Suggester {
+            options,
+            entries: vec![],
+            positions: HashMap::new()
+        }
+    }
+
+    /// Adds occurrences of a term to the dictionary
+    ///
+    /// # Arguments
+    /// * `term` - the dictionary term
+    /// * `frequency` - how often the term occurs; this is added to
+    ///   the existing frequency if the term is already known
+    pub fn insert(&mut self, term: &str, frequency: u64) {
+        match self.positions.get(term) {
+            Some(&idx) => {
+                let entry = &mut self.entries[idx];
+                entry.frequency = entry.frequency.saturating_add(frequency);
+            },
+            None => {
+                self.positions.insert(term.to_string(), self.entries.len());
+                self.entries.push(Entry {
+                    term: term.to_string(),
+                    len: term.chars().count(),
+                    frequency
+                });
+            }
+        }
+    }
+
+    /// Returns the number of distinct terms in the dictionary
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if the dictionary is empty
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns the frequency of a term, or `None` if it isn't in the dictionary
+    pub fn frequency(&self, term: &str) -> Option<u64> {
+        self.positions.get(term).map(|&idx| self.entries[idx].frequency)
+    }
+
+    /// Returns the ranked suggestions for a (possibly misspelled) word
+    ///
+    /// # Arguments
+    /// * `word` - the input to find suggestions for
+    ///
+    /// # Returns
+    /// * up to `max_suggestions` suggestions, best first; if `skip_known`
+    ///   is set and `word` is in the dictionary, no suggestions
+    pub fn suggest(&self, word: &str) -> Vec<Suggestion<'_>> {
+        if self.options.skip_known && self.positions.contains_key(word) {
+            return vec![];
+        }
+        let max_distance = self.options.max_distance;
+        let lda = LevenshteinAutomata::new(word, max_distance);
+        let word_len = word.chars().count();
+        let mut suggestions = self.entries
+            .iter()
+            // terms whose lengths differ by more than max_distance
+            // can't be accepted, so don't bother running them
+            .filter(|e| e.len.abs_diff(word_len) <= max_distance.max(0) as usize)
+            .filter(|e| lda.check(&e.term))
+            .map(|e| Suggestion {
+                term: &e.term,
+                distance: levenshtein_distance(word, &e.term),
+                frequency: e.frequency
+            })
+            .collect::<Vec<Suggestion>>();
+        suggestions.sort_by(|a, b| {
+            a.distance.cmp(&b.distance)
+                .then(b.frequency.cmp(&a.frequency))
+                .then(a.term.cmp(b.term))
+        });
+        suggestions.truncate(self.options.max_suggestions);
+        suggestions
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn basic_suggester() {
+        let mut suggester = Suggester::new(SuggestOptions {
+            max_distance: 2,
+            max_suggestions: 3,
+            skip_known: true
+        });
+        assert!(suggester.is_empty());
+        assert!(suggester.suggest("anything").is_empty());
+
+        for (term, frequency) in [("apple", 10), ("apply", 30), ("ample", 30), ("maple", 5), ("angle", 1)] {
+            suggester.insert(term, frequency);
+        }
+        suggester.insert("apple", 10);
+        assert!(suggester.len() == 5);
+        assert!(suggester.frequency("apple") == Some(20));
+        assert!(suggester.frequency("appel").is_none());
+
+        let found = suggester.suggest("appel")
+            .into_iter()
+            .map(|s| (s.term, s.distance, s.frequency))
+            .collect::<Vec<(&str, i32, u64)>>();
+        // "ample" is 3 edits away, so only two suggestions remain
+        assert!(found == vec![("apply", 2, 30), ("apple", 2, 20)]);
+
+        // known words are skipped, unless asked otherwise
+        assert!(suggester.suggest("maple").is_empty());
+        let mut suggester = Suggester::new(SuggestOptions {
+            skip_known: false,
+            ..SuggestOptions::default()
+        });
+        suggester.insert("maple", 1);
+        suggester.insert("mapped", 100);
+        let found = suggester.suggest("maple")
+            .into_iter()
+            .map(|s| s.term)
+            .collect::<Vec<&str>>();
+        assert!(found == vec!["maple", "mapped"]);
+    }
+}
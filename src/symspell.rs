@@ -0,0 +1,207 @@
+use std::collections::{HashMap, HashSet};
+use std::mem::size_of;
+use crate::levenshtein_distance_bounded;
+
+/// Memory statistics of a `SymSpellIndex`, gathered while building it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SymSpellStats {
+    /// the number of distinct terms indexed
+    pub terms: usize,
+    /// the number of distinct deletion variants, including the terms themselves
+    pub deletes: usize,
+    /// the number of (variant, term) links between them
+    pub postings: usize,
+    /// an estimate of the heap memory used by the index, in bytes
+    pub approx_bytes: usize
+}
+
+/// A symmetric-delete index over a static dictionary
+///
+/// Every term is stored under all variants obtained by deleting up to
+/// `max_distance` of its chars. At query time the same deletions are
+/// applied to the query; any term sharing a variant with it is a
+/// candidate, and candidates are then verified with the bounded
+/// Levenshtein distance. Lookups are very fast at small distances,
+/// at the cost of an index that grows quickly with `max_distance`.
+pub struct SymSpellIndex {
+    max_distance: i8,
+    terms: Vec<String>,
+    deletes: HashMap<String, Vec<u32>>,
+    stats: SymSpellStats
+}
+
+// Collects every variant of `s` with up to `max_deletes` chars removed,
+// `s` itself included
+fn deletion_variants(s: &str, max_deletes: i8) -> HashSet<String> {
+    let mut variants = HashSet::new();
+    variants.insert(s.to_string());
+    let mut frontier = vec![s.chars().collect::<Vec<char>>()];
+    for _ in 0..max_deletes {
+        let mut next = vec![];
+        for chars in frontier {
+            for i in 0..chars.len() {
+                let mut deleted = chars.clone();
+                deleted.remove(i);
+                if variants.insert(deleted.iter().collect()) {
+                    next.push(deleted);
+                }
+            }
+        }
+        frontier = next;
+    }
+    variants
+}
+
+impl SymSpellIndex {
+    /// Builds a new index
+    ///
+    /// # Arguments
+    /// * `terms` - the dictionary; duplicates are ignored
+    /// * `max_distance` - the maximum Levenshtein Distance that
+    ///   lookups will support
+    ///
+    /// # Returns
+    /// * A new `SymSpellIndex` instance
+    ///
+    /// # Examples
+    /// ```
+    /// use levenshtein_lite::SymSpellIndex;
+    /// let index = SymSpellIndex::new(["hello", "help", "world"], 1);
+    /// assert!(index.lookup("helo") == vec![("hello", 1), ("help", 1)]);
+    /// ```
+    pub fn new<'a, I>(terms: I, max_distance: i8) -> Self
+    where
+        I: IntoIterator<Item = &'a str>
+    {
+        let mut seen = HashSet::new();
+        let mut unique_terms = vec![];
+        let mut deletes: HashMap<String, Vec<u32>> = HashMap::new();
+        for term in terms {
+            if !seen.insert(term) {
+                continue;
+            }
+            let term_idx = unique_terms.len() as u32;
+            unique_terms.push(term.to_string());
+            for variant in deletion_variants(term, max_distance) {
+                deletes.entry(variant).or_default().push(term_idx);
+            }
+        }
+
+        let postings = deletes.values().map(|v| v.len()).sum::<usize>();
+        let approx_bytes = unique_terms.iter()
+            .map(|t| size_of::<String>() + t.capacity())
+            .sum::<usize>()
+            + deletes.iter()
+                .map(|(k, v)| {
+                    size_of::<String>() + k.capacity()
+                        + size_of::<Vec<u32>>() + v.capacity() * size_of::<u32>()
+                })
+                .sum::<usize>();
+        let stats = SymSpellStats {
+            terms: unique_terms.len(),
+            deletes: deletes.len(),
+            postings,
+            approx_bytes
+        };
+        SymSpellIndex {
+            max_distance,
+            terms: unique_terms,
+            deletes,
+            stats
+        }
+    }
+
+    /// Returns the memory statistics of the index
+    pub fn stats(&self) -> SymSpellStats {
+        self.stats
+    }
+
+    /// Returns the maximum distance the index was built for
+    pub fn max_distance(&self) -> i8 {
+        self.max_distance
+    }
+
+    /// Finds every term within the index's maximum distance of a query
+    ///
+    /// # Arguments
+    /// * `query` - the string to compare terms with
+    ///
+    /// # Returns
+    /// * `(term, distance)` pairs, sorted by distance, then term
+    pub fn lookup(&self, query: &str) -> Vec<(&str, i32)> {
+        let mut checked = HashSet::new();
+        let mut found = vec![];
+        for variant in deletion_variants(query, self.max_distance) {
+            let Some(term_idxs) = self.deletes.get(&variant) else {
+                continue;
+            };
+            for &term_idx in term_idxs {
+                if !checked.insert(term_idx) {
+                    continue;
+                }
+                let term = self.terms[term_idx as usize].as_str();
+                // sharing a variant only bounds the distance by the
+                // total number of deletions, so verify the candidate
+                let d = levenshtein_distance_bounded(query, term, self.max_distance as i32);
+                if let Some(d) = d {
+                    found.push((term, d));
+                }
+            }
+        }
+        found.sort_by(|a, b| (a.1, a.0).cmp(&(b.1, b.0)));
+        found
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::levenshtein_distance;
+    use rand::Rng;
+
+    #[test]
+    fn basic_index() {
+        let index = SymSpellIndex::new(["abc", "abc", "ab", ""], 1);
+        let stats = index.stats();
+        // {abc, ab, ac, bc, a, b, ""}
+        assert!(stats.terms == 3);
+        assert!(stats.deletes == 7);
+        assert!(stats.postings == 4 + 3 + 1);
+        assert!(stats.approx_bytes > 0);
+
+        assert!(index.lookup("abc") == vec![("abc", 0), ("ab", 1)]);
+        assert!(index.lookup("x") == vec![("", 1)]);
+        assert!(index.lookup("xyz").is_empty());
+
+        let index = SymSpellIndex::new(["日本語"], 2);
+        assert!(index.lookup("日") == vec![("日本語", 2)]);
+    }
+
+    #[test]
+    fn fuzz_index() {
+        let mut rng = rand::thread_rng();
+        let mut rand_string = || {
+            (0..rng.gen_range(0..7))
+                .map(|_| ['a', 'b', 'c', 'é'][rng.gen_range(0..4)])
+                .collect::<String>()
+        };
+        for max_distance in 0..4 {
+            let terms = (0..100).map(|_| rand_string()).collect::<Vec<String>>();
+            let index = SymSpellIndex::new(terms.iter().map(|t| t.as_str()), max_distance);
+            let mut unique = terms.iter().map(|t| t.as_str()).collect::<Vec<&str>>();
+            unique.sort();
+            unique.dedup();
+            for _ in 0..100 {
+                let query = rand_string();
+                let mut expected = unique
+                    .iter()
+                    .map(|&t| (t, levenshtein_distance(&query, t)))
+                    .filter(|&(_, d)| d <= max_distance as i32)
+                    .collect::<Vec<(&str, i32)>>();
+                expected.sort_by(|a, b| (a.1, a.0).cmp(&(b.1, b.0)));
+                assert!(index.lookup(&query) == expected);
+            }
+        }
+    }
+}
@@ -0,0 +1,159 @@
+use std::collections::BinaryHeap;
+use crate::{levenshtein_distance, levenshtein_distance_bounded};
+
+/// How to order candidates that are the same distance from the query
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TieBreak {
+    /// Candidates that come first in the input win
+    CandidateOrder,
+    /// Lexicographically smaller candidates win, then candidate order
+    Lexicographic
+}
+
+/// A candidate returned by `top_k`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Neighbour<'a> {
+    /// the candidate string
+    pub candidate: &'a str,
+    /// the position of the candidate in the input
+    pub index: usize,
+    /// the Levenshtein distance between the query and the candidate
+    pub distance: i32
+}
+
+// The secondary sort key of a candidate; a single search only
+// ever uses one of the variants, so they are never compared
+#[derive(PartialEq, Eq, PartialOrd, Ord)]
+enum TieKey<'a> {
+    Index(usize),
+    Lex(&'a str, usize)
+}
+
+// Heap entries, ordered so that the worst neighbour is at the top
+#[derive(PartialEq, Eq, PartialOrd, Ord)]
+struct Ranked<'a> {
+    distance: i32,
+    tie_key: TieKey<'a>,
+    candidate: &'a str,
+    index: usize
+}
+
+/// Finds the `k` candidates closest to a query
+///
+/// Once `k` candidates have been seen, the distance of the worst of them
+/// becomes the bound for `levenshtein_distance_bounded`, so the search
+/// tightens as better candidates are found.
+///
+/// # Arguments
+/// * `query` - the string to compare candidates with
+/// * `candidates` - the strings to search
+/// * `k` - the maximum number of neighbours to return
+/// * `tie_break` - how to order candidates with equal distances
+///
+/// # Returns
+/// * up to `k` neighbours, closest first
+///
+/// # Examples
+/// ```
+/// use levenshtein_lite::{top_k, TieBreak};
+/// let words = ["apple", "apply", "ample", "maple", "angle"];
+/// let best = top_k("appel", words, 2, TieBreak::Lexicographic)
+///     .into_iter()
+///     .map(|n| (n.candidate, n.distance))
+///     .collect::<Vec<(&str, i32)>>();
+/// assert!(best == vec![("apple", 2), ("apply", 2)]);
+/// ```
+pub fn top_k<'a, I>(query: &str, candidates: I, k: usize, tie_break: TieBreak) -> Vec<Neighbour<'a>>
+where
+    I: IntoIterator<Item = &'a str>
+{
+    if k == 0 {
+        return vec![];
+    }
+    let mut heap: BinaryHeap<Ranked> = BinaryHeap::with_capacity(k + 1);
+    for (index, candidate) in candidates.into_iter().enumerate() {
+        let tie_key = match tie_break {
+            TieBreak::CandidateOrder => TieKey::Index(index),
+            TieBreak::Lexicographic => TieKey::Lex(candidate, index)
+        };
+        let distance = match heap.peek() {
+            Some(worst) if heap.len() == k => {
+                // candidates further away than the current worst
+                // can never make it into the heap
+                match levenshtein_distance_bounded(query, candidate, worst.distance) {
+                    Some(d) => d,
+                    None => continue
+                }
+            },
+            _ => levenshtein_distance(query, candidate)
+        };
+        let ranked = Ranked { distance, tie_key, candidate, index };
+        if heap.len() < k {
+            heap.push(ranked);
+        } else if ranked < *heap.peek().unwrap() {
+            heap.pop();
+            heap.push(ranked);
+        }
+    }
+    heap.into_sorted_vec()
+        .into_iter()
+        .map(|r| Neighbour { candidate: r.candidate, index: r.index, distance: r.distance })
+        .collect()
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use rand::Rng;
+
+    #[test]
+    fn basic_top_k() {
+        let words = ["bcd", "abd", "abc", "abcd", "xyz"];
+        assert!(top_k("abc", words, 0, TieBreak::CandidateOrder).is_empty());
+
+        let best = top_k("abc", words, 3, TieBreak::CandidateOrder);
+        let found = best.iter().map(|n| (n.index, n.distance)).collect::<Vec<(usize, i32)>>();
+        assert!(found == vec![(2, 0), (1, 1), (3, 1)]);
+
+        let best = top_k("abc", words, 3, TieBreak::Lexicographic);
+        let found = best.iter().map(|n| n.candidate).collect::<Vec<&str>>();
+        assert!(found == vec!["abc", "abcd", "abd"]);
+
+        // asking for more than there are returns everything
+        assert!(top_k("abc", words, 10, TieBreak::CandidateOrder).len() == words.len());
+    }
+
+    #[test]
+    fn fuzz_top_k() {
+        let mut rng = rand::thread_rng();
+        let mut rand_string = || {
+            (0..rng.gen_range(0..8))
+                .map(|_| ['a', 'b', 'c'][rng.gen_range(0..3)])
+                .collect::<String>()
+        };
+        for _ in 0..200 {
+            let query = rand_string();
+            let words = (0..50).map(|_| rand_string()).collect::<Vec<String>>();
+            for tie_break in [TieBreak::CandidateOrder, TieBreak::Lexicographic] {
+                // brute force: sort everything, take the first 5
+                let mut expected = words
+                    .iter()
+                    .enumerate()
+                    .map(|(index, w)| (levenshtein_distance(&query, w), w.as_str(), index))
+                    .collect::<Vec<(i32, &str, usize)>>();
+                match tie_break {
+                    TieBreak::CandidateOrder => expected.sort_by_key(|&(d, _, i)| (d, i)),
+                    TieBreak::Lexicographic => expected.sort()
+                }
+                expected.truncate(5);
+
+                let found = top_k(&query, words.iter().map(|w| w.as_str()), 5, tie_break)
+                    .into_iter()
+                    .map(|n| (n.distance, n.candidate, n.index))
+                    .collect::<Vec<(i32, &str, usize)>>();
+                assert!(found == expected);
+            }
+        }
+    }
+}
@@ -0,0 +1,133 @@
+use std::error::Error;
+use std::fmt;
+use std::ops::RangeInclusive;
+use crate::levenshtein_distance;
+
+/// An input on which a matcher and `levenshtein_distance` disagree
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Counterexample {
+    /// the input the matcher got wrong
+    pub input: String,
+    /// the Levenshtein distance between the source and the input, or the part
+    /// of it being matched, e.g. its closest substring for `MatchMode::Infix`
+    pub distance: i32,
+    /// whether the matcher accepted the input
+    pub accepted: bool
+}
+
+impl fmt::Display for Counterexample {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let verdict = if self.accepted { "accepted" } else { "rejected" };
+        write!(f, "{} {:?}, at distance {}", verdict, self.input, self.distance)
+    }
+}
+
+impl Error for Counterexample {}
+
+/// Exhaustively checks a matcher against `levenshtein_distance`
+///
+/// Every string over `alphabet` of up to `max_len` chars is checked,
+/// shortest first, so the counterexample reported is a shortest one.
+/// There are `alphabet.len() ^ max_len` strings of the longest length,
+/// so keep both small.
+///
+/// # Arguments
+/// * `src` - the source the matcher compares inputs with
+/// * `distances` - the distances the matcher should accept
+/// * `alphabet` - the chars inputs are made of
+/// * `max_len` - the maximum length of the inputs
+/// * `check` - the matcher under test
+///
+/// # Returns
+/// * `Ok(())` if `check(input) == distances.contains(&distance)` for every input,
+///   otherwise the first input where it isn't
+///
+/// # Examples
+/// ```
+/// use levenshtein_lite::{LazyLevenshteinAutomata, verify::verify_against_dp};
+/// let mut lda = LazyLevenshteinAutomata::new("abc", 1);
+/// assert!(verify_against_dp("abc", 0..=1, &['a', 'b', 'c', 'x'], 5, |input| lda.check(input)).is_ok());
+///
+/// let err = verify_against_dp("abc", 0..=1, &['a', 'b'], 3, |input| input.len() == 3).unwrap_err();
+/// assert!(err.input == "ab" && err.distance == 1 && !err.accepted);
+/// ```
+pub fn verify_against_dp<F>(
+    src: &str,
+    distances: RangeInclusive<i8>,
+    alphabet: &[char],
+    max_len: usize,
+    check: F
+) -> Result<(), Counterexample>
+where
+    F: FnMut(&str) -> bool
+{
+    verify_with(distances, alphabet, max_len, |input| levenshtein_distance(src, input), check)
+}
+
+// Exhaustively checks a matcher against any distance to the source
+pub(crate) fn verify_with<D, F>(
+    distances: RangeInclusive<i8>,
+    alphabet: &[char],
+    max_len: usize,
+    mut distance_to: D,
+    mut check: F
+) -> Result<(), Counterexample>
+where
+    D: FnMut(&str) -> i32,
+    F: FnMut(&str) -> bool
+{
+    let mut alphabet = alphabet.to_vec();
+    alphabet.sort();
+    alphabet.dedup();
+    for input in all_strings(&alphabet, max_len) {
+        let distance = distance_to(&input);
+        let accepted = check(&input);
+        let expected = *distances.start() as i32 <= distance && distance <= *distances.end() as i32;
+        if accepted != expected {
+            return Err(Counterexample { input, distance, accepted });
+        }
+    }
+    Ok(())
+}
+
+// Every string over `alphabet` of up to `max_len` chars,
+// shortest first, then in the order of `alphabet`
+pub(crate) fn all_strings(alphabet: &[char], max_len: usize) -> Vec<String> {
+    let mut all = vec![String::new()];
+    let mut longest = vec![String::new()];
+    for _ in 0..max_len {
+        longest = longest
+            .iter()
+            .flat_map(|s| alphabet.iter().map(move |c| format!("{}{}", s, c)))
+            .collect();
+        all.extend(longest.iter().cloned());
+    }
+    all
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{BitParallelMatcher, LazyLevenshteinAutomata};
+
+    #[test]
+    fn verifies_matchers() {
+        let alphabet = ['a', 'b', 'x'];
+        for src in ["", "a", "ab", "aba", "bab"] {
+            for d in 0..=2 {
+                let mut lazy = LazyLevenshteinAutomata::new(src, d);
+                assert!(verify_against_dp(src, 0..=d, &alphabet, 5, |input| lazy.check(input)).is_ok());
+                let nfa = BitParallelMatcher::new(src, d as usize);
+                assert!(verify_against_dp(src, 0..=d, &alphabet, 5, |input| nfa.check(input)).is_ok());
+            }
+        }
+
+        // an off by one matcher is caught at the shortest input
+        let err = verify_against_dp("ab", 0..=1, &alphabet, 4, |input| {
+            levenshtein_distance("ab", input) <= 2
+        }).unwrap_err();
+        assert!(err == Counterexample { input: String::new(), distance: 2, accepted: true });
+        assert!(err.to_string() == "accepted \"\", at distance 2");
+    }
+}